@@ -0,0 +1,185 @@
+//! # Headers
+//!
+//! This module contains the [HeaderMap] that holds the headers of requests and responses, and the
+//! typed headers that parse and format the common headers of RFC 9110 and RFC 9111.
+//!
+//! A typed header is read with [Request::typed_header](crate::Request::typed_header) and written
+//! with [Response::typed_header](crate::Response::typed_header).
+//!
+//! ```rust
+//! use krustie::{ Request, Response, StatusCode, headers::{ Accept, CacheControl, CacheDirective } };
+//!
+//! fn get(request: &Request, response: &mut Response) {
+//!   if let Ok(Some(accept)) = request.typed_header::<Accept>() {
+//!     for range in accept.get_ranges() {
+//!       println!("{} ({})", range.get_item(), range.get_quality());
+//!     }
+//!   }
+//!
+//!   response
+//!     .status(StatusCode::Ok)
+//!     .typed_header(CacheControl::new().with(CacheDirective::NoStore));
+//! }
+//! ```
+
+use std::{
+    fmt::{Debug, Formatter, Result as fResult},
+    vec::IntoIter,
+};
+
+pub use accept::{Accept, AcceptEncoding, AcceptLanguage};
+pub use authorization::Authorization;
+pub use cache_control::{CacheControl, CacheDirective};
+pub use entity_tag::{ETag, EntityTag, IfMatch, IfNoneMatch};
+pub use range::{ByteRange, ContentRange, Range};
+pub use typed::{ParseHeaderError, QualityItem, TypedHeader};
+pub use vary::Vary;
+
+mod accept;
+mod authorization;
+mod cache_control;
+mod entity_tag;
+mod range;
+mod typed;
+mod vary;
+
+/// Represents the headers of a request or a response
+///
+/// Header names are case-insensitive, so `Content-Type` and `content-type` are the same header.
+/// The names are kept as they are inserted, the headers are kept in the order they are inserted
+/// and a header can have multiple values, like `Set-Cookie`.
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::HeaderMap;
+///
+/// let mut headers = HeaderMap::new();
+///
+/// headers.insert("Content-Type", "text/html");
+/// headers.append("Set-Cookie", "theme=dark");
+/// headers.append("Set-Cookie", "lang=en");
+///
+/// assert_eq!(headers.get("content-type"), Some(&"text/html".to_string()));
+/// assert_eq!(headers.get_all("set-cookie").collect::<Vec<_>>(), ["theme=dark", "lang=en"]);
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct HeaderMap {
+    entries: Vec<(String, String)>,
+}
+
+impl HeaderMap {
+    /// Returns an empty header map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the first value of the header
+    pub fn get(&self, name: &str) -> Option<&String> {
+        self.entries
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+
+    /// Returns every value of the header in order
+    pub fn get_all(&self, name: &str) -> impl Iterator<Item = &String> {
+        self.entries
+            .iter()
+            .filter(move |(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+
+    /// Checks if the header has a value
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Sets the value of the header and removes its other values
+    ///
+    /// The header keeps its position if it already has a value.
+    pub fn insert(&mut self, name: &str, value: &str) {
+        let mut found = false;
+
+        self.entries.retain_mut(|(key, current)| {
+            if !key.eq_ignore_ascii_case(name) {
+                return true;
+            }
+            if found {
+                return false;
+            }
+
+            found = true;
+            *key = name.to_string();
+            *current = value.to_string();
+            true
+        });
+
+        if !found {
+            self.append(name, value);
+        }
+    }
+
+    /// Adds a value to the header after its other values
+    pub fn append(&mut self, name: &str, value: &str) {
+        self.entries.push((name.to_string(), value.to_string()));
+    }
+
+    /// Removes the header and returns its values
+    pub fn remove(&mut self, name: &str) -> Vec<String> {
+        let (removed, entries) = std::mem::take(&mut self.entries)
+            .into_iter()
+            .partition(|(key, _)| key.eq_ignore_ascii_case(name));
+
+        self.entries = entries;
+        removed.into_iter().map(|(_, value)| value).collect()
+    }
+
+    /// Returns the names and the values of the headers in order. A header with multiple values
+    /// is returned once for each value.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+
+    /// Returns the number of values
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks if there are no headers
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Debug for HeaderMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> FromIterator<(K, V)> for HeaderMap {
+    /// Collects the headers in order. Repeated names are appended, not replaced.
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.extend(iter);
+        headers
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> Extend<(K, V)> for HeaderMap {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.append(key.as_ref(), value.as_ref());
+        }
+    }
+}
+
+impl IntoIterator for HeaderMap {
+    type Item = (String, String);
+    type IntoIter = IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
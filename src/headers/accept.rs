@@ -0,0 +1,313 @@
+use std::fmt::{Display, Formatter, Result as fResult};
+
+use crate::{
+    MediaType, Request, Response, StatusCode, request::media_type::is_token, response::ContentType,
+};
+
+use super::typed::{ParseHeaderError, QualityItem, TypedHeader, parse_quality_items, write_list};
+
+type Render = dyn Fn(&mut Response);
+
+/// Represents the `Accept` header, the media ranges that the client accepts with their qualities
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::headers::Accept;
+///
+/// let accept = Accept::try_from("text/html, application/json;q=0.9, */*;q=0.1").unwrap();
+/// let ranges = accept.get_ranges();
+///
+/// assert_eq!(ranges[1].get_item().essence(), "application/json");
+/// assert_eq!(ranges[1].get_quality(), 0.9);
+/// assert_eq!(accept.to_string(), "text/html, application/json;q=0.9, */*;q=0.1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accept {
+    ranges: Vec<QualityItem<MediaType>>,
+}
+
+impl Accept {
+    /// Returns an `Accept` header with the media ranges
+    pub fn new(ranges: Vec<QualityItem<MediaType>>) -> Self {
+        Self { ranges }
+    }
+
+    /// Returns the media ranges in order
+    pub fn get_ranges(&self) -> &[QualityItem<MediaType>] {
+        &self.ranges
+    }
+
+    /// Returns the quality of the media type. It is the quality of the most specific range that
+    /// matches the media type, so `text/html` is preferred over `text/*` and `*/*`, and `0` if no
+    /// range matches it. If several ranges are equally specific, the first one is used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ MediaType, headers::Accept };
+    ///
+    /// let accept = Accept::try_from("text/*;q=0.5, text/csv;q=0, */*;q=0.1").unwrap();
+    /// let quality = |media_type| accept.get_quality(&MediaType::try_from(media_type).unwrap());
+    ///
+    /// assert_eq!(quality("text/html"), 0.5);
+    /// assert_eq!(quality("text/csv"), 0.0);
+    /// assert_eq!(quality("image/png"), 0.1);
+    /// ```
+    pub fn get_quality(&self, media_type: &MediaType) -> f32 {
+        self.ranges
+            .iter()
+            .filter_map(|range| Some((specificity(range.get_item(), media_type)?, range)))
+            .fold(None, |best, (specificity, range)| match best {
+                Some((best_specificity, _)) if best_specificity >= specificity => best,
+                _ => Some((specificity, range)),
+            })
+            .map_or(0.0, |(_, range)| range.get_quality())
+    }
+
+    /// Returns the content type that the client prefers. It is the one with the highest quality,
+    /// and the first one in `available` if more than one of them have the same quality. It is
+    /// `None` if none of them is acceptable.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ headers::Accept, response::ContentType };
+    ///
+    /// let accept = Accept::try_from("text/html;q=0.9, application/json").unwrap();
+    /// let available = [ContentType::Html, ContentType::Json];
+    ///
+    /// assert_eq!(accept.negotiate(&available), Some(&ContentType::Json));
+    /// assert_eq!(accept.negotiate(&[ContentType::Csv]), None);
+    /// ```
+    pub fn negotiate<'a>(&self, available: &'a [ContentType]) -> Option<&'a ContentType> {
+        available
+            .iter()
+            .filter_map(|content_type| {
+                let media_type = MediaType::try_from(content_type.to_string().as_str()).ok()?;
+                let quality = self.get_quality(&media_type);
+
+                (quality > 0.0).then_some((quality, content_type))
+            })
+            .fold(
+                None,
+                |best: Option<(f32, &ContentType)>, (quality, content_type)| match best {
+                    Some((best_quality, _)) if best_quality >= quality => best,
+                    _ => Some((quality, content_type)),
+                },
+            )
+            .map(|(_, content_type)| content_type)
+    }
+}
+
+/// Returns how specific the range is if it matches the media type: `*/*` is the least specific
+/// and a range with parameters is the most specific. It is `None` if the range does not match.
+fn specificity(range: &MediaType, media_type: &MediaType) -> Option<usize> {
+    match (range.get_type(), range.get_subtype()) {
+        ("*", "*") => Some(0),
+        (main_type, "*") => (main_type == media_type.get_type()).then_some(1),
+        (main_type, subtype) => {
+            let parameters_match = range.get_parameters().iter().all(|(name, value)| {
+                media_type
+                    .get_parameter(name)
+                    .is_some_and(|current| current.eq_ignore_ascii_case(value))
+            });
+
+            (main_type == media_type.get_type()
+                && subtype == media_type.get_subtype()
+                && parameters_match)
+                .then(|| 2 + range.get_parameters().len())
+        }
+    }
+}
+
+impl TypedHeader for Accept {
+    const NAME: &'static str = "Accept";
+}
+
+impl TryFrom<&str> for Accept {
+    type Error = ParseHeaderError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse_quality_items(value, |range| MediaType::try_from(range).ok())
+            .map(Self::new)
+            .ok_or_else(|| ParseHeaderError::new(Self::NAME, value))
+    }
+}
+
+impl Display for Accept {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write_list(f, &self.ranges)
+    }
+}
+
+impl Request {
+    /// Returns the content type that the client prefers among the ones that the handler can
+    /// produce. Every content type is acceptable if the request has no valid `Accept` header, so
+    /// it is the first one. It is `None` if none of them is acceptable, which should be answered
+    /// with `406 Not Acceptable`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Request, Response, StatusCode, json::json, response::ContentType };
+    ///
+    /// fn get(request: &Request, response: &mut Response) {
+    ///   match request.negotiate(&[ContentType::Json, ContentType::Text]) {
+    ///     Some(ContentType::Json) => {
+    ///       response.status(StatusCode::Ok).body_json(json!({ "name": "John" }));
+    ///     }
+    ///     Some(_) => {
+    ///       response.status(StatusCode::Ok).body_text("John");
+    ///     }
+    ///     None => {
+    ///       response.status(StatusCode::NotAcceptable);
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    pub fn negotiate<'a>(&self, available: &'a [ContentType]) -> Option<&'a ContentType> {
+        match self.typed_header::<Accept>() {
+            Ok(Some(accept)) => accept.negotiate(available),
+            _ => available.first(),
+        }
+    }
+}
+
+impl Response {
+    /// Renders the response in the content type that the client prefers. It calls the closure of
+    /// the content type chosen by [Request::negotiate], sets `Content-Type` to it if the closure
+    /// sets a body, and adds `Accept` to the `Vary` header. The status is `406 Not Acceptable` if
+    /// none of the content types is acceptable.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Request, Response, StatusCode, json::json, response::ContentType };
+    ///
+    /// fn get(request: &Request, response: &mut Response) {
+    ///   response.status(StatusCode::Ok).format(request, &[
+    ///     (ContentType::Json, &|res| {
+    ///       res.body_json(json!({ "name": "John" }));
+    ///     }),
+    ///     (ContentType::Csv, &|res| {
+    ///       res.body_text("name\nJohn\n");
+    ///     }),
+    ///   ]);
+    /// }
+    /// ```
+    pub fn format(&mut self, request: &Request, formats: &[(ContentType, &Render)]) -> &mut Self {
+        self.vary("Accept");
+
+        let available = formats
+            .iter()
+            .map(|(content_type, _)| content_type.clone())
+            .collect::<Vec<ContentType>>();
+
+        let Some(content_type) = request.negotiate(&available) else {
+            return self.status(StatusCode::NotAcceptable);
+        };
+
+        if let Some((_, render)) = formats.iter().find(|(current, _)| current == content_type) {
+            render(self);
+        }
+
+        if !self.get_body().is_empty() {
+            self.set_header("Content-Type", &content_type.to_string());
+        }
+
+        self
+    }
+}
+
+/// Represents the `Accept-Encoding` header, the content codings that the client accepts with
+/// their qualities, e.g. `gzip, deflate;q=0.5`
+///
+/// The codings are kept in lowercase and `identity` or `*` can be one of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceptEncoding {
+    codings: Vec<QualityItem<String>>,
+}
+
+impl AcceptEncoding {
+    /// Returns an `Accept-Encoding` header with the codings
+    pub fn new(codings: Vec<QualityItem<String>>) -> Self {
+        Self { codings }
+    }
+
+    /// Returns the codings in order
+    pub fn get_codings(&self) -> &[QualityItem<String>] {
+        &self.codings
+    }
+}
+
+impl TypedHeader for AcceptEncoding {
+    const NAME: &'static str = "Accept-Encoding";
+}
+
+impl TryFrom<&str> for AcceptEncoding {
+    type Error = ParseHeaderError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse_quality_items(value, |coding| {
+            is_token(coding).then(|| coding.to_lowercase())
+        })
+        .map(Self::new)
+        .ok_or_else(|| ParseHeaderError::new(Self::NAME, value))
+    }
+}
+
+impl Display for AcceptEncoding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write_list(f, &self.codings)
+    }
+}
+
+/// Represents the `Accept-Language` header, the language ranges that the client accepts with
+/// their qualities, e.g. `en-US, en;q=0.8, *;q=0.1`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceptLanguage {
+    languages: Vec<QualityItem<String>>,
+}
+
+impl AcceptLanguage {
+    /// Returns an `Accept-Language` header with the language ranges
+    pub fn new(languages: Vec<QualityItem<String>>) -> Self {
+        Self { languages }
+    }
+
+    /// Returns the language ranges in order
+    pub fn get_languages(&self) -> &[QualityItem<String>] {
+        &self.languages
+    }
+}
+
+impl TypedHeader for AcceptLanguage {
+    const NAME: &'static str = "Accept-Language";
+}
+
+impl TryFrom<&str> for AcceptLanguage {
+    type Error = ParseHeaderError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let is_language_range = |range: &str| {
+            range == "*"
+                || range.split('-').all(|subtag| {
+                    (1..=8).contains(&subtag.len())
+                        && subtag.bytes().all(|byte| byte.is_ascii_alphanumeric())
+                })
+        };
+
+        parse_quality_items(value, |range| {
+            is_language_range(range).then(|| range.to_string())
+        })
+        .map(Self::new)
+        .ok_or_else(|| ParseHeaderError::new(Self::NAME, value))
+    }
+}
+
+impl Display for AcceptLanguage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write_list(f, &self.languages)
+    }
+}
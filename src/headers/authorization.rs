@@ -0,0 +1,178 @@
+use std::fmt::{Display, Formatter, Result as fResult};
+
+use crate::request::media_type::is_token;
+
+use super::typed::{ParseHeaderError, TypedHeader};
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Represents the `Authorization` header, the credentials of the client
+///
+/// The scheme is case-insensitive. `Basic` credentials are decoded into the username and the
+/// password, `Bearer` credentials are kept as the token and the credentials of other schemes are
+/// kept as they are.
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::headers::Authorization;
+///
+/// let authorization = Authorization::try_from("Basic a3J1c3RpZTpzZWNyZXQ=").unwrap();
+///
+/// assert_eq!(authorization, Authorization::basic("krustie", "secret"));
+/// assert_eq!(Authorization::bearer("abc.def").to_string(), "Bearer abc.def");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Authorization {
+    /// The `Basic` scheme of RFC 7617
+    Basic {
+        /// The username, which does not contain a colon
+        username: String,
+        /// The password
+        password: String,
+    },
+    /// The `Bearer` scheme of RFC 6750
+    Bearer(String),
+    /// Any other scheme
+    Other {
+        /// The name of the scheme
+        scheme: String,
+        /// The credentials after the scheme
+        credentials: String,
+    },
+}
+
+impl Authorization {
+    /// Returns `Basic` credentials
+    pub fn basic(username: &str, password: &str) -> Self {
+        Authorization::Basic {
+            username: username.to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    /// Returns `Bearer` credentials
+    pub fn bearer(token: &str) -> Self {
+        Authorization::Bearer(token.to_string())
+    }
+}
+
+impl TypedHeader for Authorization {
+    const NAME: &'static str = "Authorization";
+}
+
+impl TryFrom<&str> for Authorization {
+    type Error = ParseHeaderError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let invalid = || ParseHeaderError::new(Self::NAME, value);
+        let (scheme, credentials) = value.trim().split_once(' ').unwrap_or((value.trim(), ""));
+        let credentials = credentials.trim();
+
+        if !is_token(scheme) {
+            return Err(invalid());
+        }
+
+        match scheme.to_lowercase().as_str() {
+            "basic" => {
+                let decoded = decode_base64(credentials)
+                    .and_then(|bytes| String::from_utf8(bytes).ok())
+                    .ok_or_else(invalid)?;
+                let (username, password) = decoded.split_once(':').ok_or_else(invalid)?;
+
+                Ok(Authorization::basic(username, password))
+            }
+            "bearer" if is_token68(credentials) => Ok(Authorization::bearer(credentials)),
+            "bearer" => Err(invalid()),
+            _ => Ok(Authorization::Other {
+                scheme: scheme.to_string(),
+                credentials: credentials.to_string(),
+            }),
+        }
+    }
+}
+
+impl Display for Authorization {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        match self {
+            Authorization::Basic { username, password } => {
+                let credentials = format!("{username}:{password}");
+                write!(f, "Basic {}", encode_base64(credentials.as_bytes()))
+            }
+            Authorization::Bearer(token) => write!(f, "Bearer {token}"),
+            Authorization::Other {
+                scheme,
+                credentials,
+            } => match credentials.is_empty() {
+                true => write!(f, "{scheme}"),
+                false => write!(f, "{scheme} {credentials}"),
+            },
+        }
+    }
+}
+
+/// Checks if the value is a `token68` of RFC 9110, which bearer tokens are.
+fn is_token68(value: &str) -> bool {
+    let data = value.trim_end_matches('=');
+
+    !data.is_empty()
+        && data
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || "-._~+/".contains(char))
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |group, (index, &byte)| {
+                group | u32::from(byte) << (16 - index * 8)
+            });
+
+        for index in 0..4 {
+            match index <= chunk.len() {
+                true => encoded.push(BASE64[(group >> (18 - index * 6)) as usize & 63] as char),
+                false => encoded.push('='),
+            }
+        }
+    }
+
+    encoded
+}
+
+fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    if !input.len().is_multiple_of(4) {
+        return None;
+    }
+
+    let data = input.trim_end_matches('=');
+
+    if input.len() - data.len() > 2 {
+        return None;
+    }
+
+    let mut decoded = Vec::with_capacity(data.len() * 3 / 4);
+    let mut group = 0u32;
+
+    for (index, byte) in data.bytes().enumerate() {
+        let value = BASE64.iter().position(|&char| char == byte)? as u32;
+        group = group << 6 | value;
+
+        if index % 4 == 3 {
+            decoded.extend_from_slice(&group.to_be_bytes()[1..]);
+            group = 0;
+        }
+    }
+
+    match data.len() % 4 {
+        0 => {}
+        2 => decoded.push((group >> 4) as u8),
+        3 => decoded.extend_from_slice(&((group >> 2) as u16).to_be_bytes()),
+        _ => return None,
+    }
+
+    Some(decoded)
+}
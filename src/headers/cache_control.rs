@@ -0,0 +1,195 @@
+use std::fmt::{Display, Formatter, Result as fResult};
+
+use crate::request::media_type::is_token;
+
+use super::typed::{ParseHeaderError, TypedHeader, quote, split_list, unquote, write_list};
+
+/// Represents the `Cache-Control` header, the caching directives of RFC 9111
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::headers::{ CacheControl, CacheDirective };
+///
+/// let cache_control = CacheControl::new()
+///   .with(CacheDirective::Public)
+///   .with(CacheDirective::MaxAge(3600));
+///
+/// assert_eq!(cache_control.to_string(), "public, max-age=3600");
+///
+/// let cache_control = CacheControl::try_from("no-cache, max-age=0").unwrap();
+///
+/// assert!(cache_control.contains(&CacheDirective::NoCache));
+/// assert_eq!(cache_control.get_max_age(), Some(0));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheControl {
+    directives: Vec<CacheDirective>,
+}
+
+impl CacheControl {
+    /// Returns a `Cache-Control` header without directives
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a directive
+    pub fn with(mut self, directive: CacheDirective) -> Self {
+        self.directives.push(directive);
+        self
+    }
+
+    /// Returns the directives in order
+    pub fn get_directives(&self) -> &[CacheDirective] {
+        &self.directives
+    }
+
+    /// Checks if the header has the directive
+    pub fn contains(&self, directive: &CacheDirective) -> bool {
+        self.directives.contains(directive)
+    }
+
+    /// Returns the value of the `max-age` directive
+    pub fn get_max_age(&self) -> Option<u64> {
+        self.directives
+            .iter()
+            .find_map(|directive| match directive {
+                CacheDirective::MaxAge(seconds) => Some(*seconds),
+                _ => None,
+            })
+    }
+}
+
+impl TypedHeader for CacheControl {
+    const NAME: &'static str = "Cache-Control";
+}
+
+impl TryFrom<&str> for CacheControl {
+    type Error = ParseHeaderError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        split_list(value)
+            .into_iter()
+            .map(CacheDirective::parse)
+            .collect::<Option<Vec<CacheDirective>>>()
+            .map(|directives| Self { directives })
+            .ok_or_else(|| ParseHeaderError::new(Self::NAME, value))
+    }
+}
+
+impl Display for CacheControl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write_list(f, &self.directives)
+    }
+}
+
+/// Represents a directive of the `Cache-Control` header
+///
+/// The field names of `no-cache="..."` and `private="..."` are not kept, so those directives
+/// apply to the whole response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheDirective {
+    /// `max-age=<seconds>`
+    MaxAge(u64),
+    /// `s-maxage=<seconds>`
+    SMaxAge(u64),
+    /// `max-stale` with an optional number of seconds
+    MaxStale(Option<u64>),
+    /// `min-fresh=<seconds>`
+    MinFresh(u64),
+    /// `stale-while-revalidate=<seconds>`
+    StaleWhileRevalidate(u64),
+    /// `stale-if-error=<seconds>`
+    StaleIfError(u64),
+    /// `no-cache`
+    NoCache,
+    /// `no-store`
+    NoStore,
+    /// `no-transform`
+    NoTransform,
+    /// `only-if-cached`
+    OnlyIfCached,
+    /// `must-revalidate`
+    MustRevalidate,
+    /// `must-understand`
+    MustUnderstand,
+    /// `proxy-revalidate`
+    ProxyRevalidate,
+    /// `private`
+    Private,
+    /// `public`
+    Public,
+    /// `immutable`
+    Immutable,
+    /// Any other directive with its optional value
+    Extension(String, Option<String>),
+}
+
+impl CacheDirective {
+    fn parse(directive: &str) -> Option<Self> {
+        let (name, value) = match directive.split_once('=') {
+            Some((name, value)) => (name.trim(), Some(unquote(value.trim())?)),
+            None => (directive.trim(), None),
+        };
+
+        if !is_token(name) {
+            return None;
+        }
+
+        let seconds = || value.as_deref()?.parse::<u64>().ok();
+
+        let directive = match (name.to_lowercase().as_str(), &value) {
+            ("max-age", _) => CacheDirective::MaxAge(seconds()?),
+            ("s-maxage", _) => CacheDirective::SMaxAge(seconds()?),
+            ("max-stale", None) => CacheDirective::MaxStale(None),
+            ("max-stale", Some(_)) => CacheDirective::MaxStale(Some(seconds()?)),
+            ("min-fresh", _) => CacheDirective::MinFresh(seconds()?),
+            ("stale-while-revalidate", _) => CacheDirective::StaleWhileRevalidate(seconds()?),
+            ("stale-if-error", _) => CacheDirective::StaleIfError(seconds()?),
+            ("no-cache", _) => CacheDirective::NoCache,
+            ("no-store", None) => CacheDirective::NoStore,
+            ("no-transform", None) => CacheDirective::NoTransform,
+            ("only-if-cached", None) => CacheDirective::OnlyIfCached,
+            ("must-revalidate", None) => CacheDirective::MustRevalidate,
+            ("must-understand", None) => CacheDirective::MustUnderstand,
+            ("proxy-revalidate", None) => CacheDirective::ProxyRevalidate,
+            ("private", _) => CacheDirective::Private,
+            ("public", None) => CacheDirective::Public,
+            ("immutable", None) => CacheDirective::Immutable,
+            (name, _) => CacheDirective::Extension(name.to_string(), value),
+        };
+
+        Some(directive)
+    }
+}
+
+impl Display for CacheDirective {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        match self {
+            CacheDirective::MaxAge(seconds) => write!(f, "max-age={seconds}"),
+            CacheDirective::SMaxAge(seconds) => write!(f, "s-maxage={seconds}"),
+            CacheDirective::MaxStale(None) => write!(f, "max-stale"),
+            CacheDirective::MaxStale(Some(seconds)) => write!(f, "max-stale={seconds}"),
+            CacheDirective::MinFresh(seconds) => write!(f, "min-fresh={seconds}"),
+            CacheDirective::StaleWhileRevalidate(seconds) => {
+                write!(f, "stale-while-revalidate={seconds}")
+            }
+            CacheDirective::StaleIfError(seconds) => write!(f, "stale-if-error={seconds}"),
+            CacheDirective::NoCache => write!(f, "no-cache"),
+            CacheDirective::NoStore => write!(f, "no-store"),
+            CacheDirective::NoTransform => write!(f, "no-transform"),
+            CacheDirective::OnlyIfCached => write!(f, "only-if-cached"),
+            CacheDirective::MustRevalidate => write!(f, "must-revalidate"),
+            CacheDirective::MustUnderstand => write!(f, "must-understand"),
+            CacheDirective::ProxyRevalidate => write!(f, "proxy-revalidate"),
+            CacheDirective::Private => write!(f, "private"),
+            CacheDirective::Public => write!(f, "public"),
+            CacheDirective::Immutable => write!(f, "immutable"),
+            CacheDirective::Extension(name, None) => write!(f, "{name}"),
+            CacheDirective::Extension(name, Some(value)) if is_token(value) => {
+                write!(f, "{name}={value}")
+            }
+            CacheDirective::Extension(name, Some(value)) => write!(f, "{name}={}", quote(value)),
+        }
+    }
+}
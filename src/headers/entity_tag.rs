@@ -0,0 +1,267 @@
+use std::fmt::{Display, Formatter, Result as fResult};
+
+use super::typed::{ParseHeaderError, TypedHeader, split_list, write_list};
+
+/// Represents an entity tag of RFC 9110, e.g. `"v1"` or `W/"v1"`
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::headers::EntityTag;
+///
+/// let strong = EntityTag::strong("v1");
+/// let weak = EntityTag::try_from("W/\"v1\"").unwrap();
+///
+/// assert!(weak.is_weak());
+/// assert!(strong.weak_eq(&weak));
+/// assert!(!strong.strong_eq(&weak));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntityTag {
+    tag: String,
+    weak: bool,
+}
+
+impl EntityTag {
+    /// Returns a strong entity tag
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tag contains a double quote or a character that is not visible ASCII.
+    pub fn strong(tag: &str) -> Self {
+        assert!(is_entity_tag(tag), "Invalid entity tag: {tag}");
+
+        Self {
+            tag: tag.to_string(),
+            weak: false,
+        }
+    }
+
+    /// Returns a weak entity tag
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tag contains a double quote or a character that is not visible ASCII.
+    pub fn weak(tag: &str) -> Self {
+        Self {
+            weak: true,
+            ..Self::strong(tag)
+        }
+    }
+
+    /// Returns the tag without the quotes
+    pub fn get_tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Checks if the entity tag is weak
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Compares the entity tags with the strong comparison, which is used by `If-Match`. Both of
+    /// them must be strong.
+    pub fn strong_eq(&self, other: &EntityTag) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Compares the entity tags with the weak comparison, which is used by `If-None-Match`
+    pub fn weak_eq(&self, other: &EntityTag) -> bool {
+        self.tag == other.tag
+    }
+}
+
+impl TryFrom<&str> for EntityTag {
+    type Error = ParseHeaderError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (weak, quoted) = match value.trim().strip_prefix("W/") {
+            Some(quoted) => (true, quoted),
+            None => (false, value.trim()),
+        };
+
+        match quoted
+            .strip_prefix('"')
+            .and_then(|quoted| quoted.strip_suffix('"'))
+        {
+            Some(tag) if is_entity_tag(tag) => Ok(Self {
+                tag: tag.to_string(),
+                weak,
+            }),
+            _ => Err(ParseHeaderError::new(ETag::NAME, value)),
+        }
+    }
+}
+
+impl Display for EntityTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        match self.weak {
+            true => write!(f, "W/\"{}\"", self.tag),
+            false => write!(f, "\"{}\"", self.tag),
+        }
+    }
+}
+
+/// Represents the `ETag` header, the entity tag of the response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ETag {
+    tag: EntityTag,
+}
+
+impl ETag {
+    /// Returns an `ETag` header with the entity tag
+    pub fn new(tag: EntityTag) -> Self {
+        Self { tag }
+    }
+
+    /// Returns the entity tag
+    pub fn get_tag(&self) -> &EntityTag {
+        &self.tag
+    }
+}
+
+impl TypedHeader for ETag {
+    const NAME: &'static str = "ETag";
+}
+
+impl TryFrom<&str> for ETag {
+    type Error = ParseHeaderError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        EntityTag::try_from(value).map(Self::new)
+    }
+}
+
+impl Display for ETag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write!(f, "{}", self.tag)
+    }
+}
+
+/// Represents the `If-Match` header, which is `*` or a list of entity tags
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfMatch {
+    /// `*`, which matches any current representation
+    Any,
+    /// The entity tags
+    Tags(Vec<EntityTag>),
+}
+
+impl IfMatch {
+    /// Checks if the entity tag of the current representation matches with the strong
+    /// comparison
+    pub fn matches(&self, current: &EntityTag) -> bool {
+        match self {
+            IfMatch::Any => true,
+            IfMatch::Tags(tags) => tags.iter().any(|tag| tag.strong_eq(current)),
+        }
+    }
+}
+
+impl TypedHeader for IfMatch {
+    const NAME: &'static str = "If-Match";
+}
+
+impl TryFrom<&str> for IfMatch {
+    type Error = ParseHeaderError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match parse_tags(value) {
+            Some(None) => Ok(IfMatch::Any),
+            Some(Some(tags)) => Ok(IfMatch::Tags(tags)),
+            None => Err(ParseHeaderError::new(Self::NAME, value)),
+        }
+    }
+}
+
+impl Display for IfMatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        match self {
+            IfMatch::Any => write!(f, "*"),
+            IfMatch::Tags(tags) => write_list(f, tags),
+        }
+    }
+}
+
+/// Represents the `If-None-Match` header, which is `*` or a list of entity tags
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::{ Request, Response, StatusCode, headers::{ ETag, EntityTag, IfNoneMatch } };
+///
+/// fn get(request: &Request, response: &mut Response) {
+///   let current = EntityTag::strong("v2");
+///
+///   if let Ok(Some(if_none_match)) = request.typed_header::<IfNoneMatch>() {
+///     if if_none_match.matches(&current) {
+///       response.status(StatusCode::NotModified);
+///       return;
+///     }
+///   }
+///
+///   response.status(StatusCode::Ok).typed_header(ETag::new(current)).body_text("v2");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfNoneMatch {
+    /// `*`, which matches any current representation
+    Any,
+    /// The entity tags
+    Tags(Vec<EntityTag>),
+}
+
+impl IfNoneMatch {
+    /// Checks if the entity tag of the current representation matches with the weak comparison
+    pub fn matches(&self, current: &EntityTag) -> bool {
+        match self {
+            IfNoneMatch::Any => true,
+            IfNoneMatch::Tags(tags) => tags.iter().any(|tag| tag.weak_eq(current)),
+        }
+    }
+}
+
+impl TypedHeader for IfNoneMatch {
+    const NAME: &'static str = "If-None-Match";
+}
+
+impl TryFrom<&str> for IfNoneMatch {
+    type Error = ParseHeaderError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match parse_tags(value) {
+            Some(None) => Ok(IfNoneMatch::Any),
+            Some(Some(tags)) => Ok(IfNoneMatch::Tags(tags)),
+            None => Err(ParseHeaderError::new(Self::NAME, value)),
+        }
+    }
+}
+
+impl Display for IfNoneMatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        match self {
+            IfNoneMatch::Any => write!(f, "*"),
+            IfNoneMatch::Tags(tags) => write_list(f, tags),
+        }
+    }
+}
+
+/// Parses `*` into `Some(None)` and a list of entity tags into `Some(Some(tags))`.
+fn parse_tags(value: &str) -> Option<Option<Vec<EntityTag>>> {
+    if value.trim() == "*" {
+        return Some(None);
+    }
+
+    split_list(value)
+        .into_iter()
+        .map(|tag| EntityTag::try_from(tag).ok())
+        .collect::<Option<Vec<EntityTag>>>()
+        .map(Some)
+}
+
+/// Checks if the tag is made of `etagc` characters. Characters outside of ASCII are not allowed,
+/// as header values are decoded as ISO-8859-1.
+fn is_entity_tag(tag: &str) -> bool {
+    tag.chars()
+        .all(|char| char.is_ascii_graphic() && char != '"')
+}
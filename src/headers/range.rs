@@ -0,0 +1,212 @@
+use std::fmt::{Display, Formatter, Result as fResult};
+
+use super::typed::{ParseHeaderError, TypedHeader, split_list, write_list};
+
+/// Represents the `Range` header, the byte ranges that the client requests
+///
+/// Only the `bytes` unit is supported.
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::headers::{ ByteRange, Range };
+///
+/// let range = Range::try_from("bytes=0-99, 500-, -10").unwrap();
+///
+/// assert_eq!(
+///   range.get_ranges(),
+///   [ByteRange::FromTo(0, 99), ByteRange::From(500), ByteRange::Last(10)]
+/// );
+/// assert_eq!(range.get_ranges()[2].resolve(1000), Some((990, 999)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Range {
+    ranges: Vec<ByteRange>,
+}
+
+impl Range {
+    /// Returns a `Range` header with the byte ranges
+    pub fn new(ranges: Vec<ByteRange>) -> Self {
+        Self { ranges }
+    }
+
+    /// Returns the byte ranges in order
+    pub fn get_ranges(&self) -> &[ByteRange] {
+        &self.ranges
+    }
+}
+
+impl TypedHeader for Range {
+    const NAME: &'static str = "Range";
+}
+
+impl TryFrom<&str> for Range {
+    type Error = ParseHeaderError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let invalid = || ParseHeaderError::new(Self::NAME, value);
+        let (unit, ranges) = value.trim().split_once('=').ok_or_else(invalid)?;
+
+        if !unit.trim().eq_ignore_ascii_case("bytes") {
+            return Err(invalid());
+        }
+
+        let ranges = split_list(ranges)
+            .into_iter()
+            .map(ByteRange::parse)
+            .collect::<Option<Vec<ByteRange>>>()
+            .ok_or_else(invalid)?;
+
+        match ranges.is_empty() {
+            true => Err(invalid()),
+            false => Ok(Self::new(ranges)),
+        }
+    }
+}
+
+impl Display for Range {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write!(f, "bytes=")?;
+        write_list(f, &self.ranges)
+    }
+}
+
+/// Represents a range of the `Range` header. The positions are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// `<first>-<last>`
+    FromTo(u64, u64),
+    /// `<first>-`, from the position to the end
+    From(u64),
+    /// `-<length>`, the last bytes
+    Last(u64),
+}
+
+impl ByteRange {
+    /// Returns the first and the last positions of the range in a representation of the length.
+    /// It is `None` if the range is not satisfiable.
+    pub fn resolve(&self, length: u64) -> Option<(u64, u64)> {
+        match *self {
+            ByteRange::FromTo(first, last) if first < length => Some((first, last.min(length - 1))),
+            ByteRange::From(first) if first < length => Some((first, length - 1)),
+            ByteRange::Last(suffix) if suffix > 0 && length > 0 => {
+                Some((length.saturating_sub(suffix), length - 1))
+            }
+            _ => None,
+        }
+    }
+
+    fn parse(range: &str) -> Option<Self> {
+        let (first, last) = range.split_once('-')?;
+        let position = |value: &str| match value.bytes().all(|byte| byte.is_ascii_digit()) {
+            true => value.parse::<u64>().ok(),
+            false => None,
+        };
+
+        match (first.trim(), last.trim()) {
+            ("", last) => Some(ByteRange::Last(position(last)?)),
+            (first, "") => Some(ByteRange::From(position(first)?)),
+            (first, last) => {
+                let (first, last) = (position(first)?, position(last)?);
+                (first <= last).then_some(ByteRange::FromTo(first, last))
+            }
+        }
+    }
+}
+
+impl Display for ByteRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        match self {
+            ByteRange::FromTo(first, last) => write!(f, "{first}-{last}"),
+            ByteRange::From(first) => write!(f, "{first}-"),
+            ByteRange::Last(suffix) => write!(f, "-{suffix}"),
+        }
+    }
+}
+
+/// Represents the `Content-Range` header, the range of a partial response
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::headers::ContentRange;
+///
+/// assert_eq!(ContentRange::bytes(0, 99, Some(1000)).to_string(), "bytes 0-99/1000");
+/// assert_eq!(ContentRange::Unsatisfied(1000).to_string(), "bytes */1000");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentRange {
+    /// The positions of the range and the length of the representation, if it is known
+    Bytes {
+        /// The first position, inclusive
+        first: u64,
+        /// The last position, inclusive
+        last: u64,
+        /// The length of the whole representation
+        length: Option<u64>,
+    },
+    /// The length of the representation, for a `416 Range Not Satisfiable` response
+    Unsatisfied(u64),
+}
+
+impl ContentRange {
+    /// Returns the `Content-Range` of the bytes between the positions
+    pub fn bytes(first: u64, last: u64, length: Option<u64>) -> Self {
+        ContentRange::Bytes {
+            first,
+            last,
+            length,
+        }
+    }
+}
+
+impl TypedHeader for ContentRange {
+    const NAME: &'static str = "Content-Range";
+}
+
+impl TryFrom<&str> for ContentRange {
+    type Error = ParseHeaderError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let invalid = || ParseHeaderError::new(Self::NAME, value);
+        let (unit, range) = value.trim().split_once(' ').ok_or_else(invalid)?;
+        let (range, length) = range.trim().split_once('/').ok_or_else(invalid)?;
+
+        if !unit.eq_ignore_ascii_case("bytes") {
+            return Err(invalid());
+        }
+
+        let length = match length {
+            "*" => None,
+            length => Some(length.parse::<u64>().map_err(|_| invalid())?),
+        };
+
+        match (range, length) {
+            ("*", Some(length)) => Ok(ContentRange::Unsatisfied(length)),
+            (range, length) => match ByteRange::parse(range) {
+                Some(ByteRange::FromTo(first, last)) => {
+                    Ok(ContentRange::bytes(first, last, length))
+                }
+                _ => Err(invalid()),
+            },
+        }
+    }
+}
+
+impl Display for ContentRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        match self {
+            ContentRange::Bytes {
+                first,
+                last,
+                length: Some(length),
+            } => write!(f, "bytes {first}-{last}/{length}"),
+            ContentRange::Bytes {
+                first,
+                last,
+                length: None,
+            } => write!(f, "bytes {first}-{last}/*"),
+            ContentRange::Unsatisfied(length) => write!(f, "bytes */{length}"),
+        }
+    }
+}
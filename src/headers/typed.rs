@@ -0,0 +1,289 @@
+use std::fmt::{Display, Formatter, Result as fResult};
+
+use crate::{Request, Response};
+
+/// A header that can be parsed from its value and formatted back into it
+///
+/// The value of a repeated header is joined with `, ` before it is parsed, as list headers like
+/// `Accept` can be split into multiple lines.
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::headers::{ ParseHeaderError, TypedHeader };
+/// use std::fmt::{ Display, Formatter, Result };
+///
+/// struct RequestId(u64);
+///
+/// impl TypedHeader for RequestId {
+///   const NAME: &'static str = "X-Request-Id";
+/// }
+///
+/// impl TryFrom<&str> for RequestId {
+///   type Error = ParseHeaderError;
+///
+///   fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+///     value
+///       .parse()
+///       .map(RequestId)
+///       .map_err(|_| ParseHeaderError::new(Self::NAME, value))
+///   }
+/// }
+///
+/// impl Display for RequestId {
+///   fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+///     write!(f, "{}", self.0)
+///   }
+/// }
+/// ```
+pub trait TypedHeader: for<'a> TryFrom<&'a str, Error = ParseHeaderError> + Display {
+    /// The name of the header, e.g. `Cache-Control`
+    const NAME: &'static str;
+}
+
+impl Request {
+    /// Parses the header into a typed header. It is `None` if the request does not have the
+    /// header.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [ParseHeaderError] if the value of the header is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Request, Response, StatusCode, headers::Authorization };
+    ///
+    /// fn get(request: &Request, response: &mut Response) {
+    ///   match request.typed_header::<Authorization>() {
+    ///     Ok(Some(Authorization::Bearer(token))) => {
+    ///       response.status(StatusCode::Ok);
+    ///     }
+    ///     _ => {
+    ///       response.status(StatusCode::Unauthorized);
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    pub fn typed_header<H: TypedHeader>(&self) -> Result<Option<H>, ParseHeaderError> {
+        let values = self
+            .get_headers()
+            .get_all(H::NAME)
+            .map(String::as_str)
+            .collect::<Vec<&str>>();
+
+        match values.is_empty() {
+            true => Ok(None),
+            false => H::try_from(values.join(", ").as_str()).map(Some),
+        }
+    }
+}
+
+impl Response {
+    /// Sets a typed header. It replaces the values that the header already has.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Request, Response, StatusCode, headers::{ CacheControl, CacheDirective } };
+    ///
+    /// fn get(request: &Request, response: &mut Response) {
+    ///   let cache_control = CacheControl::new()
+    ///     .with(CacheDirective::Public)
+    ///     .with(CacheDirective::MaxAge(3600));
+    ///
+    ///   response.status(StatusCode::Ok).typed_header(cache_control);
+    /// }
+    /// ```
+    pub fn typed_header<H: TypedHeader>(&mut self, header: H) -> &mut Self {
+        self.set_header(H::NAME, &header.to_string())
+    }
+}
+
+/// Represents an item of a list header with its quality value, e.g. `text/html;q=0.8`
+///
+/// The quality is between `0` and `1` with at most three decimals. `0` means that the item is
+/// not acceptable and `1` is the default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QualityItem<T> {
+    item: T,
+    quality: u16,
+}
+
+impl<T> QualityItem<T> {
+    /// Returns an item with the quality, which is clamped between `0` and `1`
+    pub fn new(item: T, quality: f32) -> Self {
+        Self {
+            item,
+            quality: (quality.clamp(0.0, 1.0) * 1000.0).round() as u16,
+        }
+    }
+
+    /// Returns the item
+    pub fn get_item(&self) -> &T {
+        &self.item
+    }
+
+    /// Returns the quality of the item
+    pub fn get_quality(&self) -> f32 {
+        f32::from(self.quality) / 1000.0
+    }
+}
+
+impl<T: Display> Display for QualityItem<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        match self.quality {
+            1000 => write!(f, "{}", self.item),
+            0 => write!(f, "{};q=0", self.item),
+            quality => {
+                let decimals = format!("{quality:03}");
+                write!(f, "{};q=0.{}", self.item, decimals.trim_end_matches('0'))
+            }
+        }
+    }
+}
+
+/// Parses the items of a list header with their `q` parameters. The parameters before `q` are
+/// part of the item.
+pub(crate) fn parse_quality_items<T>(
+    value: &str,
+    parse_item: impl Fn(&str) -> Option<T>,
+) -> Option<Vec<QualityItem<T>>> {
+    split_list(value)
+        .into_iter()
+        .map(|element| {
+            let (item, quality) = match find_quality(element) {
+                Some((index, quality)) => (&element[..index], parse_quality(quality)?),
+                None => (element, 1000),
+            };
+
+            Some(QualityItem {
+                item: parse_item(item.trim())?,
+                quality,
+            })
+        })
+        .collect()
+}
+
+/// Returns the index of the `;` before the `q` parameter and the value of the parameter.
+fn find_quality(element: &str) -> Option<(usize, &str)> {
+    element.match_indices(';').find_map(|(index, _)| {
+        let (name, value) = element[index + 1..].split_once('=')?;
+
+        name.trim()
+            .eq_ignore_ascii_case("q")
+            .then(|| (index, value.split(';').next().unwrap_or_default().trim()))
+    })
+}
+
+/// Parses a quality value of RFC 9110 into thousandths, e.g. `0.5` into `500`.
+fn parse_quality(value: &str) -> Option<u16> {
+    let (integer, decimals) = value.split_once('.').unwrap_or((value, ""));
+
+    if decimals.len() > 3 || !decimals.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let thousandths = format!("{decimals:0<3}").parse::<u16>().ok()?;
+
+    match integer {
+        "0" => Some(thousandths),
+        "1" if thousandths == 0 => Some(1000),
+        _ => None,
+    }
+}
+
+/// Splits a list header into its elements. Commas in quoted strings do not split and empty
+/// elements are skipped.
+pub(crate) fn split_list(value: &str) -> Vec<&str> {
+    let mut elements = Vec::new();
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (index, char) in value.char_indices() {
+        match char {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                elements.push(value[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    elements.push(value[start..].trim());
+    elements.retain(|element| !element.is_empty());
+    elements
+}
+
+/// Writes the items of a list header separated by `, `.
+pub(crate) fn write_list<T: Display>(f: &mut Formatter<'_>, items: &[T]) -> fResult {
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{item}")?;
+    }
+
+    Ok(())
+}
+
+/// Returns the value of a token or a quoted string. Returns `None` if a quoted string is not
+/// closed.
+pub(crate) fn unquote(value: &str) -> Option<String> {
+    let Some(quoted) = value.strip_prefix('"') else {
+        return Some(value.to_string());
+    };
+    let quoted = quoted.strip_suffix('"')?;
+
+    let mut unquoted = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => unquoted.push(chars.next()?),
+            '"' => return None,
+            _ => unquoted.push(char),
+        }
+    }
+
+    Some(unquoted)
+}
+
+/// Returns the value as a quoted string.
+pub(crate) fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Error for parsing a typed header
+///
+/// This error is returned when the value of a header is not valid for its type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseHeaderError {
+    name: String,
+    value: String,
+}
+
+impl ParseHeaderError {
+    /// Returns an error for the value of the header
+    pub fn new(name: &str, value: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    /// Returns the name of the header
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Display for ParseHeaderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write!(f, "Invalid {} header: {}", self.name, self.value)
+    }
+}
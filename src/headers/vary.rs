@@ -0,0 +1,89 @@
+use std::fmt::{Display, Formatter, Result as fResult};
+
+use crate::{Response, request::media_type::is_token};
+
+use super::typed::{ParseHeaderError, TypedHeader, split_list, write_list};
+
+/// Represents the `Vary` header, the request headers that the response depends on
+///
+/// The names are case-insensitive and `*` means that the response depends on more than the
+/// headers.
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::headers::Vary;
+///
+/// let vary = Vary::try_from("Accept-Encoding").unwrap().with("Accept").with("accept");
+///
+/// assert!(vary.contains("accept"));
+/// assert_eq!(vary.to_string(), "Accept-Encoding, Accept");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vary {
+    names: Vec<String>,
+}
+
+impl Vary {
+    /// Returns a `Vary` header without names
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a header name if it is not already in the list
+    pub fn with(mut self, name: &str) -> Self {
+        if !self.contains(name) {
+            self.names.push(name.to_string());
+        }
+        self
+    }
+
+    /// Returns the header names in order
+    pub fn get_names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Checks if the header name is in the list
+    pub fn contains(&self, name: &str) -> bool {
+        self.names
+            .iter()
+            .any(|current| current.eq_ignore_ascii_case(name))
+    }
+}
+
+impl TypedHeader for Vary {
+    const NAME: &'static str = "Vary";
+}
+
+impl TryFrom<&str> for Vary {
+    type Error = ParseHeaderError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let names = split_list(value);
+
+        match names.iter().all(|name| is_token(name)) {
+            true => Ok(names.into_iter().fold(Vary::new(), Vary::with)),
+            false => Err(ParseHeaderError::new(Self::NAME, value)),
+        }
+    }
+}
+
+impl Display for Vary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write_list(f, &self.names)
+    }
+}
+
+impl Response {
+    /// Adds a header name to the `Vary` header, keeping the names that are already in it
+    pub(crate) fn vary(&mut self, name: &str) -> &mut Self {
+        let vary = self
+            .get_headers()
+            .get_all(Vary::NAME)
+            .filter_map(|value| Vary::try_from(value.as_str()).ok())
+            .flat_map(|vary| vary.get_names().to_vec())
+            .fold(Vary::new(), |vary, name| vary.with(&name));
+
+        self.typed_header(vary.with(name))
+    }
+}
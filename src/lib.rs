@@ -76,6 +76,7 @@
 //! This will create an executable file in the `target/debug` directory.
 //!
 
+pub mod headers;
 pub mod json;
 #[doc(hidden)]
 pub mod middleware;
@@ -91,16 +92,33 @@ pub mod server;
 pub mod middlewares {
     pub use crate::middleware::GzipEncoder;
     pub use crate::middleware::RateLimiter;
+    pub use crate::middleware::ServeOpenApi;
     pub use crate::middleware::ServeStatic;
 }
 
+#[doc(inline)]
+pub use headers::HeaderMap;
 #[doc(inline)]
 pub use middleware::Middleware;
 #[doc(inline)]
+pub use middleware::Next;
+#[doc(inline)]
+pub use request::body::BodyLimits;
+#[doc(inline)]
 pub use request::builder::RequestBuilder;
 #[doc(inline)]
+pub use request::extract::ExtractError;
+#[doc(inline)]
+pub use request::extract::ExtractSource;
+#[doc(inline)]
 pub use request::http_method::HttpMethod;
 #[doc(inline)]
+pub use request::media_type::MediaType;
+#[doc(inline)]
+pub use request::multipart::Multipart;
+#[doc(inline)]
+pub use request::multipart::UploadedFile;
+#[doc(inline)]
 pub use request::Request;
 #[doc(inline)]
 pub use request::RequestBody;
@@ -109,10 +127,30 @@ pub use response::status_code::StatusCode;
 #[doc(inline)]
 pub use response::Response;
 #[doc(inline)]
+pub use router::builder::RouteBuilder;
+#[doc(inline)]
 pub use router::endpoint::Endpoint;
 #[doc(inline)]
 pub use router::Router;
 #[doc(inline)]
+pub use router::RouterError;
+#[doc(inline)]
+pub use router::TrailingSlash;
+#[doc(inline)]
+pub use router::host::HostRouter;
+#[doc(inline)]
+pub use router::openapi::RouteDoc;
+#[doc(inline)]
+pub use router::resource::Resource;
+#[doc(inline)]
+pub use router::resource::ResourceAction;
+#[doc(inline)]
+pub use router::table::RouteInfo;
+#[doc(inline)]
+pub use router::table::RouteTable;
+#[doc(inline)]
+pub use router::url::UrlError;
+#[doc(inline)]
 pub use server::route_handler::HandlerResult;
 #[doc(inline)]
 pub use server::route_handler::RouteHandler;
@@ -1,13 +1,18 @@
 use dyn_clone::DynClone;
-use std::fmt::Debug;
+use std::fmt::{Debug, Formatter, Result as fResult};
 
 use crate::{HandlerResult, Request, Response, RouteHandler};
 
 pub mod gzip;
+pub mod openapi;
 pub mod rate_limiter;
 pub mod statics;
 
-pub use self::{gzip::GzipEncoder, rate_limiter::RateLimiter, statics::ServeStatic};
+pub use self::{
+    gzip::GzipEncoder, openapi::ServeOpenApi, rate_limiter::RateLimiter, statics::ServeStatic,
+};
+
+pub(crate) type Continuation<'a> = dyn FnMut(&Request, &mut Response) -> HandlerResult + 'a;
 
 #[doc = include_str!("../docs/core/middleware.md")]
 pub trait Middleware: DynClone + Send {
@@ -16,7 +21,102 @@ pub trait Middleware: DynClone + Send {
     /// For the middleware to be executed and continue the execution, it should return [HandlerResult::Next].
     ///
     /// If the middleware should stop the execution (e.g. return 404), it should return [HandlerResult::End].
-    fn middleware(&mut self, request: &Request, response: &mut Response) -> HandlerResult;
+    ///
+    /// Middlewares that implement [Middleware::around] don't need to implement this function.
+    fn middleware(&mut self, request: &Request, response: &mut Response) -> HandlerResult {
+        let _ = (request, response);
+        HandlerResult::Next
+    }
+
+    /// Runs the middleware around the handlers that come after it. The handlers are run by
+    /// calling [Next::run], so the middleware can run code both before and after them, e.g. to
+    /// measure the time of the request or to compress the response.
+    ///
+    /// By default, it runs [Middleware::middleware] and then the next handlers if it returns
+    /// [HandlerResult::Next].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ HandlerResult, Middleware, Request, Response, middleware::Next };
+    /// use std::time::Instant;
+    ///
+    /// #[derive(Clone)]
+    /// struct Timer;
+    ///
+    /// impl Middleware for Timer {
+    ///   fn around(&mut self, request: &Request, response: &mut Response, next: Next) -> HandlerResult {
+    ///     let start = Instant::now();
+    ///     let result = next.run(request, response);
+    ///
+    ///     response.set_header("X-Response-Time", &format!("{}ms", start.elapsed().as_millis()));
+    ///     result
+    ///   }
+    /// }
+    /// ```
+    fn around(&mut self, request: &Request, response: &mut Response, next: Next) -> HandlerResult {
+        match self.middleware(request, response) {
+            HandlerResult::End => HandlerResult::End,
+            HandlerResult::Next => next.run(request, response),
+        }
+    }
+
+    /// Returns the name of the middleware that is shown in the route table and in the debug
+    /// output. By default, it is the name of the type without its module path.
+    fn name(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        let end = name.find('<').unwrap_or(name.len());
+
+        match name[..end].rfind("::") {
+            Some(start) => &name[start + 2..],
+            None => name,
+        }
+    }
+}
+
+/// Continuation that runs the handlers that come after a middleware.
+///
+/// It is passed to [Middleware::around] and can be run only once.
+pub struct Next<'a> {
+    continuation: &'a mut Continuation<'a>,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(continuation: &'a mut Continuation<'a>) -> Self {
+        Self { continuation }
+    }
+
+    /// Runs the next handlers and returns their result.
+    ///
+    /// The request can be replaced with a modified copy for the next handlers.
+    pub fn run(self, request: &Request, response: &mut Response) -> HandlerResult {
+        (self.continuation)(request, response)
+    }
+}
+
+impl Debug for Next<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write!(f, "Next")
+    }
+}
+
+/// Runs the middlewares around `last`, the first middleware being the outermost one.
+pub(crate) fn run_middlewares(
+    middlewares: &mut [Box<dyn Middleware>],
+    request: &Request,
+    response: &mut Response,
+    last: &mut Continuation<'_>,
+) -> HandlerResult {
+    match middlewares.split_first_mut() {
+        Some((middleware, rest)) => {
+            let mut continuation = |request: &Request, response: &mut Response| {
+                run_middlewares(rest, request, response, last)
+            };
+
+            middleware.around(request, response, Next::new(&mut continuation))
+        }
+        None => last(request, response),
+    }
 }
 
 impl<T> RouteHandler for T
@@ -26,11 +126,20 @@ where
     fn handle(&mut self, request: &Request, response: &mut Response) -> HandlerResult {
         T::middleware(self, request, response)
     }
+
+    fn handle_with_next(
+        &mut self,
+        request: &Request,
+        response: &mut Response,
+        next: Next,
+    ) -> HandlerResult {
+        T::around(self, request, response, next)
+    }
 }
 
 impl Debug for dyn Middleware {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Middleware",)
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write!(f, "{}", self.name())
     }
 }
 
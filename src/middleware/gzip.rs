@@ -1,11 +1,18 @@
 use flate2::{write::GzEncoder, Compression};
 use std::io::Write;
 
-use super::Middleware;
-use crate::{request::Request, response::Response, server::route_handler::HandlerResult};
+use super::{Middleware, Next};
+use crate::{
+    headers::AcceptEncoding, request::Request, response::Response,
+    server::route_handler::HandlerResult,
+};
 
 /// A middleware for compressing response body using gzip.
 ///
+/// The response is compressed after the rest of the handlers are run, so it can be added before
+/// or after the routers. `Accept-Encoding` is added to the `Vary` header of every response that
+/// could be compressed.
+///
 /// # Example
 ///
 /// ```rust
@@ -38,23 +45,28 @@ impl GzipEncoder {
 }
 
 impl Middleware for GzipEncoder {
-    fn middleware(&mut self, request: &Request, response: &mut Response) -> HandlerResult {
-        let body = response.get_body_mut();
+    fn around(&mut self, request: &Request, response: &mut Response, next: Next) -> HandlerResult {
+        let result = next.run(request, response);
 
-        if body.is_empty() {
-            return HandlerResult::Next;
+        if response.get_body().is_empty() || response.get_header("Content-Encoding").is_some() {
+            return result;
         }
 
-        if let Some(str_encodings) = request.get_header("accept-encoding") {
-            let encodings = str_encodings
-                .split(',')
-                .map(|item| item.trim())
-                .collect::<Vec<&str>>();
+        response.vary("Accept-Encoding");
+
+        if let Ok(Some(accept_encoding)) = request.typed_header::<AcceptEncoding>() {
+            let codings = accept_encoding.get_codings();
+            let gzip = codings
+                .iter()
+                .find(|coding| coding.get_item() == "gzip")
+                .or_else(|| codings.iter().find(|coding| coding.get_item() == "*"));
 
-            if !encodings.contains(&"gzip") {
-                return HandlerResult::Next;
+            if !gzip.is_some_and(|coding| coding.get_quality() > 0.0) {
+                return result;
             }
 
+            let body = response.get_body_mut();
+
             match Self::encode(body) {
                 Ok(compressed_bytes) => {
                     response.set_header("Content-Encoding", "gzip");
@@ -67,6 +79,6 @@ impl Middleware for GzipEncoder {
             }
         }
 
-        return HandlerResult::Next;
+        return result;
     }
 }
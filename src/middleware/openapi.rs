@@ -0,0 +1,55 @@
+use crate::{
+    json::JsonValue, server::route_handler::HandlerResult, HttpMethod, Middleware, Request,
+    Response, StatusCode,
+};
+
+/// Serves an OpenAPI document, e.g. the one generated by [Router::openapi](crate::Router::openapi).
+///
+/// It responds to the `GET` requests for its path and passes every other request to the next
+/// handlers.
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::{ Router, Server, StatusCode, middlewares::ServeOpenApi };
+///
+/// let mut server = Server::create();
+/// let mut router = Router::new();
+///
+/// router.get("/users/:id", |req, res| {
+///   res.status(StatusCode::Ok);
+/// });
+///
+/// let document = router.openapi("Users API", "1.0.0");
+///
+/// server.use_handler(ServeOpenApi::new("/openapi.json", document));
+/// server.use_handler(router);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ServeOpenApi {
+    path: String,
+    document: JsonValue,
+}
+
+impl ServeOpenApi {
+    /// Creates a new instance of ServeOpenApi that serves the document at the path
+    pub fn new(path: &str, document: JsonValue) -> Self {
+        Self {
+            path: path.to_string(),
+            document,
+        }
+    }
+}
+
+impl Middleware for ServeOpenApi {
+    fn middleware(&mut self, request: &Request, response: &mut Response) -> HandlerResult {
+        if *request.get_method() != HttpMethod::GET || request.get_path() != self.path {
+            return HandlerResult::Next;
+        }
+
+        response
+            .status(StatusCode::Ok)
+            .body_json(self.document.clone());
+        HandlerResult::End
+    }
+}
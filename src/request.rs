@@ -1,31 +1,47 @@
 use self::{http_method::HttpMethod, request_line::RequestLine};
+use crate::headers::HeaderMap;
 use std::{
     collections::HashMap,
     fmt::{Debug, Display, Formatter, Result as fResult},
     net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Arc,
 };
 
-pub use body::RequestBody;
+pub use body::{BodyLimits, RequestBody};
+pub use extract::{ExtractError, ExtractSource};
 
 pub mod body;
 pub mod builder;
+pub(crate) mod charset;
+pub mod extract;
 pub mod http_method;
+pub mod media_type;
+pub mod multipart;
 pub(crate) mod parser;
 mod request_line;
+pub(crate) mod uri;
 
 #[doc = include_str!("../docs/core/request.md")]
 #[derive(Clone)]
 pub struct Request {
+    parts: Arc<Parts>,
+    params: HashMap<String, String>,
+}
+
+/// The parts of a request that are parsed from the connection. They are shared by the copies of
+/// the request that routers make to add the route parameters.
+#[derive(Clone)]
+struct Parts {
     request: RequestLine,
-    headers: HashMap<String, String>,
+    headers: HeaderMap,
     queries: HashMap<String, String>,
-    params: HashMap<String, String>,
+    query_values: HashMap<String, Vec<String>>,
     body: RequestBody,
     peer_addr: SocketAddr,
 }
 
 impl Request {
-    /// Returns the reference of the Request headers as a HashMap
+    /// Returns the reference of the Request headers as a [HeaderMap]
     ///
     /// # Example
     ///
@@ -35,13 +51,15 @@ impl Request {
     /// fn get(request: &Request, response: &mut Response) {
     ///   let headers = request.get_headers();
     ///   let content_type = headers.get("content-type");
+    ///   let cookies = headers.get_all("cookie").collect::<Vec<_>>();
     /// }
     /// ```
-    pub fn get_headers(&self) -> &HashMap<String, String> {
-        &self.headers
+    pub fn get_headers(&self) -> &HeaderMap {
+        &self.parts.headers
     }
 
-    /// Returns the value of the requested header
+    /// Returns the value of the requested header. The name is case-insensitive and the first
+    /// value is returned if the header is repeated.
     ///
     /// # Example
     ///
@@ -53,7 +71,7 @@ impl Request {
     /// }
     /// ```
     pub fn get_header(&self, key: &str) -> Option<&str> {
-        self.headers.get(key).map(|v| v.as_str())
+        self.parts.headers.get(key).map(|v| v.as_str())
     }
 
     /// Returns the body of the HTTP request
@@ -80,7 +98,7 @@ impl Request {
     /// }
     /// ```
     pub fn get_body(&self) -> &RequestBody {
-        &self.body
+        &self.parts.body
     }
 
     /// Returns the peer address of the HTTP request
@@ -98,16 +116,21 @@ impl Request {
     /// }
     /// ```
     pub fn get_peer_addr(&self) -> &SocketAddr {
-        &self.peer_addr
+        &self.parts.peer_addr
     }
 
     /// Returns the queries of the HTTP request as a HashMap
     ///
+    /// The keys and the values are percent-decoded and `+` is decoded as a space. If a key is
+    /// repeated, the map holds its first value. A key without a value, like `debug` in
+    /// `/hello?debug`, has an empty value.
+    ///
     /// | The path of the HTTP request | Value |
     /// | -- | -- |
     /// | `/hello` | `[]` |
     /// | `/hello?planet=earth` | `[{ "planet": "earth" }]` |
     /// | `/hello?planet=earth&moon=luna` | `[{ "planet": "earth" }, { "moon": "luna"}]` |
+    /// | `/hello?city=new+york` | `[{ "city": "new york" }]` |
     ///
     /// # Example
     ///
@@ -123,7 +146,7 @@ impl Request {
     /// }
     /// ```
     pub fn get_query_params(&self) -> &HashMap<String, String> {
-        &self.queries
+        &self.parts.queries
     }
 
     /// Returns the query parameter of the HTTP request
@@ -144,17 +167,39 @@ impl Request {
     /// }
     /// ```
     pub fn get_query_param(&self, key: &str) -> Option<&String> {
-        self.queries.get(key)
+        self.parts.queries.get(key)
+    }
+
+    /// Returns every value of a query parameter in order, or an empty slice if there is none
+    ///
+    /// | The path of the HTTP request | get_query_all(key: &str) | Returns |
+    /// | -- | -- | -- |
+    /// | `/posts?tag=rust&tag=web` | `get_query_all("tag")` | `["rust", "web"]` |
+    /// | `/posts?tag=rust` | `get_query_all("tag")` | `["rust"]` |
+    /// | `/posts` | `get_query_all("tag")` | `[]` |
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::Request;
+    ///
+    /// let request = Request::builder().path("/posts?tag=rust&tag=web%20dev").build();
+    ///
+    /// assert_eq!(request.get_query_all("tag"), ["rust", "web dev"]);
+    /// ```
+    pub fn get_query_all(&self, key: &str) -> &[String] {
+        self.parts.query_values.get(key).map_or(&[], Vec::as_slice)
     }
 
-    /// Returns the path of the HTTP request as a Vector
+    /// Returns the percent-decoded segments of the path of the HTTP request as a Vector
     ///
     /// | The path of the HTTP request | `get_path_array()` |
     /// | -- | -- |
-    /// | `/` | `vec![]` |
+    /// | `/` | `vec![""]` |
     /// | `/hello` | `vec!["hello"]` |
     /// | `/hello/world` | `vec!["hello", "world"]` |
-    /// | `/hello/world?city=istanbul` | `vec!["hello", "world?city=istanbul"]` |
+    /// | `/hello/world?city=istanbul` | `vec!["hello", "world"]` |
+    /// | `/hello/new%20york` | `vec!["hello", "new york"]` |
     ///
     /// # Example
     ///
@@ -166,22 +211,54 @@ impl Request {
     /// }
     /// ```
     pub fn get_path_array(&self) -> &Vec<String> {
-        self.request.get_path_array()
+        self.parts.request.get_path_array()
     }
 
-    /// Returns the path of the HTTP request as a String
+    /// Returns the path of the HTTP request without the query and the fragment. The path is not
+    /// percent-decoded.
+    ///
+    /// For an absolute-form request target, e.g. `http://example.com/hello`, it is the path after
+    /// the host, `/hello`.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use krustie::{ Request, Response };
+    /// use krustie::Request;
     ///
-    /// fn get(request: &Request, response: &mut Response) {
-    ///   let path: &str = request.get_path();
-    /// }
+    /// let request = Request::builder().path("/hello/world?city=istanbul").build();
+    ///
+    /// assert_eq!(request.get_path(), "/hello/world");
     /// ```
     pub fn get_path(&self) -> &str {
-        self.request.get_path()
+        self.parts.request.get_path()
+    }
+
+    /// Returns the query string of the HTTP request without the `?`, as it is in the request
+    /// target. It is `None` if the request target has no `?`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::Request;
+    ///
+    /// let request = Request::builder().path("/search?q=new+york&page=2").build();
+    ///
+    /// assert_eq!(request.get_raw_query(), Some("q=new+york&page=2"));
+    /// ```
+    pub fn get_raw_query(&self) -> Option<&str> {
+        self.parts.request.get_query()
+    }
+
+    /// Returns the authority of the HTTP request target when it is in absolute-form, e.g.
+    /// `example.com:8080` for `GET http://example.com:8080/users HTTP/1.1`.
+    pub(crate) fn get_authority(&self) -> Option<&str> {
+        self.parts.request.get_authority()
+    }
+
+    /// Returns the fragment of the HTTP request target without the `#`. Browsers do not send the
+    /// fragment, so it is usually `None`.
+    pub fn get_fragment(&self) -> Option<&str> {
+        self.parts.request.get_fragment()
     }
 
     /// Returns the requested parameter of the HTTP request
@@ -223,7 +300,7 @@ impl Request {
     /// }
     /// ```
     pub fn get_version(&self) -> &str {
-        &self.request.get_version()
+        self.parts.request.get_version()
     }
 
     /// Returns the method of the HTTP request
@@ -244,44 +321,76 @@ impl Request {
     /// }
     /// ```
     pub fn get_method(&self) -> &HttpMethod {
-        self.request.get_method()
+        self.parts.request.get_method()
+    }
+
+    /// Returns a copy of the request with more route parameters. The other parts of the request
+    /// are shared, not copied.
+    pub(crate) fn with_params(&self, params: impl IntoIterator<Item = (String, String)>) -> Self {
+        let mut request = Self {
+            parts: Arc::clone(&self.parts),
+            params: self.params.clone(),
+        };
+
+        request.params.extend(params);
+        request
     }
 
-    pub(crate) fn add_param(&mut self, params: HashMap<String, String>) {
-        self.params = params;
+    fn parts_mut(&mut self) -> &mut Parts {
+        Arc::make_mut(&mut self.parts)
+    }
+
+    pub(crate) fn set_queries(&mut self, queries: Vec<(String, String)>) {
+        let parts = self.parts_mut();
+
+        parts.queries.clear();
+        parts.query_values.clear();
+
+        for (key, value) in queries {
+            parts
+                .queries
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+            parts.query_values.entry(key).or_default().push(value);
+        }
     }
 }
 
 impl Default for Request {
     fn default() -> Self {
         Self {
-            request: RequestLine::new("GET", "/", "HTTP/1.1")
-                .expect("Failed to create default RequestLine"),
-            queries: HashMap::new(),
-            headers: HashMap::new(),
+            parts: Arc::new(Parts {
+                request: RequestLine::new("GET", "/", "HTTP/1.1")
+                    .expect("Failed to create default RequestLine"),
+                queries: HashMap::new(),
+                query_values: HashMap::new(),
+                headers: HeaderMap::new(),
+                body: RequestBody::None,
+                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 0),
+            }),
             params: HashMap::new(),
-            body: RequestBody::None,
-            peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 0),
         }
     }
 }
 
 impl Debug for Request {
     fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
-        fn format_hashmap(value: &HashMap<String, String>) -> String {
+        fn format_hashmap<'a>(value: impl IntoIterator<Item = (&'a String, &'a String)>) -> String {
             value
-                .iter()
+                .into_iter()
                 .map(|(k, v)| format!("  {}: {}", k, v))
                 .collect::<Vec<String>>()
                 .join("\r\n")
         }
 
-        let headers = format_hashmap(&self.headers);
+        let headers = format_hashmap(self.parts.headers.iter());
         let params = format_hashmap(&self.params);
-        let queries = format_hashmap(&self.queries);
-        let body = match &self.body {
+        let queries = format_hashmap(&self.parts.queries);
+        let body = match &self.parts.body {
             RequestBody::Text(string) => format!("{:?}", string),
             RequestBody::Json(json) => format!("{:?}", json),
+            RequestBody::Form(form) => format!("{:?}", form),
+            RequestBody::Multipart(multipart) => format!("{:?}", multipart),
             RequestBody::Binary(body) => format!("{:?}", body),
             RequestBody::None => "None".to_string(),
         };
@@ -289,12 +398,7 @@ impl Debug for Request {
         write!(
             f,
             "From:\r\n  {}\r\nRequest Line:\r\n  {}\r\nHeaders:\r\n{}\r\nParams:\r\n{}\r\nQueries:\r\n{}\r\nBody:\r\n{}",
-            self.peer_addr,
-            self.request,
-            headers,
-            params,
-            queries,
-            body
+            self.parts.peer_addr, self.parts.request, headers, params, queries, body
         )
     }
 }
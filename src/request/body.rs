@@ -1,7 +1,12 @@
-use std::io::{Error, ErrorKind};
+use std::{
+    collections::HashMap,
+    io::{Error, ErrorKind},
+};
 
 use crate::json::JsonValue;
 
+use super::{charset::Charset, media_type::MediaType, multipart::Multipart, uri::parse_urlencoded};
+
 // TODO: Add doctests
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// Represents the body of the HTTP request
@@ -10,6 +15,8 @@ use crate::json::JsonValue;
 /// - Binary: Represents a binary body.
 /// - Text: Represents a text body.
 /// - Json: Represents a json body.
+/// - Form: Represents an `application/x-www-form-urlencoded` body.
+/// - Multipart: Represents a `multipart/form-data` body.
 /// - None: Represents that there is no body or a body that is not supported.
 pub enum RequestBody {
     /// Represents a binary body.
@@ -18,28 +25,142 @@ pub enum RequestBody {
     Text(String),
     /// Represents a json body.
     Json(JsonValue),
-    // Represents a form body. Holds a HashMap of strings. **Not implemented yet.**
-    // Form(HashMap<String, String>),
+    /// Represents an `application/x-www-form-urlencoded` body. Holds the decoded values of every
+    /// field in order, since a field can be repeated.
+    Form(HashMap<String, Vec<String>>),
+    /// Represents a `multipart/form-data` body with its text fields and files.
+    Multipart(Multipart),
     /// Represents that there is no body or a body that is not supported.
     None,
 }
 
 impl RequestBody {
-    pub(crate) fn parse(body: &[u8], content_type: &str) -> Result<RequestBody, Error> {
+    /// Parses the body by its media type. Bodies without a media type are binary.
+    ///
+    /// JSON is recognized for `application/json` and `application/*+json`, and text for every
+    /// `text/*` type, which is decoded with its `charset`.
+    pub(crate) fn parse(
+        body: &[u8],
+        content_type: Option<&MediaType>,
+    ) -> Result<RequestBody, Error> {
         if body.is_empty() {
             return Ok(RequestBody::None);
         }
 
-        match content_type {
-            "application/json" => match serde_json::from_slice(&body[..]) {
+        let Some(media_type) = content_type else {
+            return Ok(RequestBody::Binary(body.to_vec()));
+        };
+
+        let charset = || match media_type.get_charset() {
+            Some(label) => Charset::from_label(label).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::Unsupported,
+                    format!("Unsupported charset: {label}"),
+                )
+            }),
+            None => Ok(Charset::Utf8),
+        };
+
+        match (media_type.get_type(), media_type.get_subtype()) {
+            _ if media_type.is_json() => match serde_json::from_slice(body) {
                 Ok(json) => Ok(RequestBody::Json(json)),
                 Err(_) => Err(Error::new(
                     ErrorKind::InvalidData,
                     "Error while parsing json body",
                 )),
             },
-            "plain/text" => Ok(RequestBody::Text(body.iter().map(|&c| c as char).collect())),
+            ("application", "x-www-form-urlencoded") => match parse_urlencoded(body, charset()?) {
+                Ok(fields) => Ok(RequestBody::Form(fields.into_iter().fold(
+                    HashMap::new(),
+                    |mut form: HashMap<String, Vec<String>>, (key, value)| {
+                        form.entry(key).or_default().push(value);
+                        form
+                    },
+                ))),
+                Err(err) => Err(Error::new(ErrorKind::InvalidData, err.to_string())),
+            },
+            ("text", _) => match charset()?.decode(body.to_vec()) {
+                Some(text) => Ok(RequestBody::Text(text)),
+                None => Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "Text body is not valid in its charset",
+                )),
+            },
             _ => Ok(RequestBody::Binary(body.to_vec())),
         }
     }
 }
+
+/// The size limits of the request bodies
+///
+/// A request whose body is larger than `max_body_size`, or with a `multipart/form-data` file that
+/// is larger than `max_file_size` or a text field that is larger than `max_field_size`, gets
+/// `413 Payload Too Large`. By default, a body can be 16 MiB, a file 8 MiB and a text field 1 MiB.
+/// Text fields are kept in memory, so their limit is lower.
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::{ BodyLimits, Server };
+///
+/// let mut server = Server::create();
+///
+/// server.body_limits(
+///   BodyLimits::new()
+///     .max_body_size(64 * 1024 * 1024)
+///     .max_file_size(32 * 1024 * 1024),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyLimits {
+    max_body_size: usize,
+    max_file_size: usize,
+    max_field_size: usize,
+}
+
+impl BodyLimits {
+    /// Creates the default limits
+    pub fn new() -> Self {
+        Self {
+            max_body_size: 16 * 1024 * 1024,
+            max_file_size: 8 * 1024 * 1024,
+            max_field_size: 1024 * 1024,
+        }
+    }
+
+    /// Sets the maximum size of a body in bytes
+    pub fn max_body_size(mut self, bytes: usize) -> Self {
+        self.max_body_size = bytes;
+        self
+    }
+
+    /// Sets the maximum size of a file in a `multipart/form-data` body in bytes
+    pub fn max_file_size(mut self, bytes: usize) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+
+    /// Sets the maximum size of a text field in a `multipart/form-data` body in bytes
+    pub fn max_field_size(mut self, bytes: usize) -> Self {
+        self.max_field_size = bytes;
+        self
+    }
+
+    pub(crate) fn get_max_body_size(&self) -> usize {
+        self.max_body_size
+    }
+
+    pub(crate) fn get_max_file_size(&self) -> usize {
+        self.max_file_size
+    }
+
+    pub(crate) fn get_max_field_size(&self) -> usize {
+        self.max_field_size
+    }
+}
+
+impl Default for BodyLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -30,6 +30,10 @@ impl RequestBuilder {
     /// assert_eq!(request.get_path(), "/echo/hello");
     /// assert_eq!(request.get_version(), "HTTP/1.1");
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path is not a valid request target, see [RequestBuilder::path].
     pub fn request_line(&mut self, method: HttpMethod, path: &str, version: &str) -> &mut Self {
         self.request.set_method(method);
         self.request.set_uri(path);
@@ -68,6 +72,11 @@ impl RequestBuilder {
     ///
     /// assert_eq!(request.get_path(), "/echo/hello");
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path is not a valid request target, e.g. it does not start with `/` or has
+    /// an invalid percent-encoding such as `/users/%zz` or `/search?q=%zz`.
     pub fn path(&mut self, path: &str) -> &mut Self {
         self.request.set_uri(path);
         self
@@ -187,23 +196,26 @@ impl Request {
     }
 
     fn set_method(&mut self, method: HttpMethod) {
-        self.request.set_method(method);
+        self.parts_mut().request.set_method(method);
     }
 
     fn set_uri(&mut self, path: &str) {
-        self.request.set_uri(path);
-        self.queries = Request::parse_queries(&self.request.get_path_array());
+        self.parts_mut().request.set_uri(path);
+        let queries = Request::parse_queries(self.parts.request.get_query())
+            .unwrap_or_else(|err| panic!("{err}"));
+
+        self.set_queries(queries);
     }
 
     fn set_version(&mut self, version: &str) {
-        self.request.set_version(version);
+        self.parts_mut().request.set_version(version);
     }
 
     fn set_header(&mut self, key: &str, value: &str) {
-        self.headers.insert(key.to_string(), value.to_string());
+        self.parts_mut().headers.insert(key, value);
     }
 
     fn set_body(&mut self, body: RequestBody) {
-        self.body = body;
+        self.parts_mut().body = body;
     }
 }
@@ -0,0 +1,32 @@
+/// A character encoding of a request body, from the `charset` parameter of its `Content-Type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum Charset {
+    #[default]
+    Utf8,
+    Latin1,
+    Ascii,
+}
+
+impl Charset {
+    /// Returns the charset for a label, e.g. `utf-8` or `iso-8859-1`. The label is
+    /// case-insensitive.
+    pub(crate) fn from_label(label: &str) -> Option<Self> {
+        match label.trim().trim_matches('"').to_lowercase().as_str() {
+            "utf-8" | "utf8" => Some(Charset::Utf8),
+            "us-ascii" | "ascii" => Some(Charset::Ascii),
+            "iso-8859-1" | "iso8859-1" | "latin1" | "l1" => Some(Charset::Latin1),
+            _ => None,
+        }
+    }
+
+    /// Decodes the bytes into a string. Returns `None` if they are not valid in the charset.
+    pub(crate) fn decode(&self, bytes: Vec<u8>) -> Option<String> {
+        match self {
+            Charset::Utf8 => String::from_utf8(bytes).ok(),
+            Charset::Latin1 => Some(bytes.into_iter().map(char::from).collect()),
+            Charset::Ascii => bytes
+                .is_ascii()
+                .then(|| bytes.into_iter().map(char::from).collect()),
+        }
+    }
+}
@@ -0,0 +1,479 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Result as fResult},
+};
+
+use serde::{
+    de::{
+        self,
+        value::{MapDeserializer, SeqDeserializer},
+        DeserializeOwned, IntoDeserializer, Unexpected, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer,
+};
+
+use crate::{json::json, json::JsonValue, Response, StatusCode};
+
+use super::{Request, RequestBody};
+
+impl Request {
+    /// Deserializes the parameters of the route into `T`
+    ///
+    /// Numbers and booleans are parsed from the parameters, so the fields of `T` can have any type
+    /// that deserializes from a string, a number or a boolean.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [ExtractError] with `400 Bad Request` if a parameter is missing or invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Request, Response, StatusCode };
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct UserPath {
+    ///   id: u32,
+    /// }
+    ///
+    /// fn get(request: &Request, response: &mut Response) {
+    ///   match request.params::<UserPath>() {
+    ///     Ok(path) => {
+    ///       response.status(StatusCode::Ok).body_text(&path.id.to_string());
+    ///     }
+    ///     Err(err) => err.respond(response),
+    ///   }
+    /// }
+    /// ```
+    pub fn params<T: DeserializeOwned>(&self) -> Result<T, ExtractError> {
+        from_map(
+            single_values(&self.params),
+            ExtractSource::Params,
+            StatusCode::BadRequest,
+        )
+    }
+
+    /// Deserializes the query string into `T`
+    ///
+    /// Optional fields can be left out of the query string. A field with a sequence type, like
+    /// `Vec<String>`, gets every value of a repeated key, and needs `#[serde(default)]` to be left
+    /// out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [ExtractError] with `400 Bad Request` if a query parameter is missing or
+    /// invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Request, Response, StatusCode };
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Pagination {
+    ///   page: u32,
+    ///   limit: Option<u32>,
+    ///   #[serde(default)]
+    ///   tag: Vec<String>,
+    /// }
+    ///
+    /// fn get(request: &Request, response: &mut Response) {
+    ///   let Ok(pagination) = request.query::<Pagination>() else {
+    ///     response.status(StatusCode::BadRequest);
+    ///     return;
+    ///   };
+    /// }
+    /// ```
+    pub fn query<T: DeserializeOwned>(&self) -> Result<T, ExtractError> {
+        from_map(
+            self.parts
+                .query_values
+                .iter()
+                .map(|(key, values)| (key.as_str(), values.as_slice())),
+            ExtractSource::Query,
+            StatusCode::BadRequest,
+        )
+    }
+
+    /// Deserializes the headers into `T`. The names of the headers are lowercase, so the fields
+    /// of `T` are usually renamed with `#[serde(rename_all = "kebab-case")]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [ExtractError] with `400 Bad Request` if a header is missing or invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Request, Response };
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// #[serde(rename_all = "kebab-case")]
+    /// struct Auth {
+    ///   x_api_key: String,
+    /// }
+    ///
+    /// fn get(request: &Request, response: &mut Response) {
+    ///   let auth = match request.headers::<Auth>() {
+    ///     Ok(auth) => auth,
+    ///     Err(err) => return err.respond(response),
+    ///   };
+    /// }
+    /// ```
+    pub fn headers<T: DeserializeOwned>(&self) -> Result<T, ExtractError> {
+        let headers = self.parts.headers.iter().fold(
+            HashMap::new(),
+            |mut headers: HashMap<String, Vec<String>>, (key, value)| {
+                headers
+                    .entry(key.to_lowercase())
+                    .or_default()
+                    .push(value.clone());
+                headers
+            },
+        );
+
+        from_map(
+            headers
+                .iter()
+                .map(|(key, values)| (key.as_str(), values.as_slice())),
+            ExtractSource::Headers,
+            StatusCode::BadRequest,
+        )
+    }
+
+    /// Deserializes the JSON body into `T`
+    ///
+    /// # Errors
+    ///
+    /// Returns an [ExtractError] with `400 Bad Request` if the body is not JSON and with
+    /// `422 Unprocessable Entity` if the JSON does not have the shape of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Request, Response, StatusCode };
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct NewUser {
+    ///   name: String,
+    ///   age: u8,
+    /// }
+    ///
+    /// fn post(request: &Request, response: &mut Response) {
+    ///   match request.json::<NewUser>() {
+    ///     Ok(user) => {
+    ///       response.status(StatusCode::Created).body_text(&user.name);
+    ///     }
+    ///     Err(err) => err.respond(response),
+    ///   }
+    /// }
+    /// ```
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, ExtractError> {
+        let RequestBody::Json(json) = &self.parts.body else {
+            return Err(ExtractError {
+                source: ExtractSource::Body,
+                field: None,
+                reason: "expected a JSON body".to_string(),
+                status: StatusCode::BadRequest,
+            });
+        };
+
+        serde_path_to_error::deserialize(json).map_err(|err| {
+            ExtractError::new(ExtractSource::Body, StatusCode::UnprocessableEntity, err)
+        })
+    }
+
+    /// Deserializes the `application/x-www-form-urlencoded` body, or the text fields of a
+    /// `multipart/form-data` body, into `T`
+    ///
+    /// Like [Request::query], numbers and booleans are parsed from the fields and a field with a
+    /// sequence type gets every value of a repeated field.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [ExtractError] with `400 Bad Request` if the body is not a form and with
+    /// `422 Unprocessable Entity` if a field is missing or invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Request, Response, StatusCode };
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Login {
+    ///   username: String,
+    ///   remember_me: Option<bool>,
+    /// }
+    ///
+    /// fn post(request: &Request, response: &mut Response) {
+    ///   match request.form::<Login>() {
+    ///     Ok(login) => {
+    ///       response.status(StatusCode::Ok).body_text(&login.username);
+    ///     }
+    ///     Err(err) => err.respond(response),
+    ///   }
+    /// }
+    /// ```
+    pub fn form<T: DeserializeOwned>(&self) -> Result<T, ExtractError> {
+        let form = match &self.parts.body {
+            RequestBody::Form(form) => form,
+            RequestBody::Multipart(multipart) => multipart.get_fields(),
+            _ => {
+                return Err(ExtractError {
+                    source: ExtractSource::Body,
+                    field: None,
+                    reason: "expected a form body".to_string(),
+                    status: StatusCode::BadRequest,
+                });
+            }
+        };
+
+        from_map(
+            form.iter()
+                .map(|(key, values)| (key.as_str(), values.as_slice())),
+            ExtractSource::Body,
+            StatusCode::UnprocessableEntity,
+        )
+    }
+}
+
+fn single_values(map: &HashMap<String, String>) -> impl Iterator<Item = (&str, &[String])> {
+    map.iter()
+        .map(|(key, value)| (key.as_str(), std::slice::from_ref(value)))
+}
+
+fn from_map<'de, T: DeserializeOwned>(
+    map: impl Iterator<Item = (&'de str, &'de [String])>,
+    source: ExtractSource,
+    status: StatusCode,
+) -> Result<T, ExtractError> {
+    let deserializer = MapDeserializer::<_, de::value::Error>::new(
+        map.map(|(key, values)| (key, StrValue(values))),
+    );
+
+    serde_path_to_error::deserialize(deserializer)
+        .map_err(|err| ExtractError::new(source, status, err))
+}
+
+/// The values of a parameter, a query parameter, a header or a form field. The first value is parsed when the
+/// type of the field is a number or a boolean, and all of them are used for a sequence.
+struct StrValue<'de>(&'de [String]);
+
+impl<'de> StrValue<'de> {
+    fn first(&self) -> &'de str {
+        self.0.first().map_or("", String::as_str)
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self.first().trim().parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => Err(de::Error::invalid_value(Unexpected::Str(self.first()), &visitor)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for StrValue<'de> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_borrowed_str(self.first())
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(SeqDeserializer::new(
+            self.0
+                .iter()
+                .map(|value| StrValue(std::slice::from_ref(value))),
+        ))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(self.first().into_deserializer())
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct tuple tuple_struct map struct identifier
+        ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, de::value::Error> for StrValue<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// The part of the request that a value is extracted from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtractSource {
+    /// The parameters of the route
+    Params,
+    /// The query string
+    Query,
+    /// The headers
+    Headers,
+    /// The body
+    Body,
+}
+
+impl Display for ExtractSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        match self {
+            ExtractSource::Params => write!(f, "params"),
+            ExtractSource::Query => write!(f, "query"),
+            ExtractSource::Headers => write!(f, "headers"),
+            ExtractSource::Body => write!(f, "body"),
+        }
+    }
+}
+
+/// Represents an error that occurs when extracting a typed value from a request
+///
+/// [ExtractError::respond] writes the error to the response as JSON:
+///
+/// ```json
+/// {
+///   "error": "Unprocessable Entity",
+///   "source": "body",
+///   "field": "address.city",
+///   "reason": "invalid type: integer `42`, expected a string"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractError {
+    source: ExtractSource,
+    field: Option<String>,
+    reason: String,
+    status: StatusCode,
+}
+
+impl ExtractError {
+    fn new<E: Display>(
+        source: ExtractSource,
+        status: StatusCode,
+        err: serde_path_to_error::Error<E>,
+    ) -> Self {
+        let path = err.path().to_string();
+        let path = (path != ".").then_some(path);
+        let reason = err.into_inner().to_string();
+
+        let missing = reason
+            .strip_prefix("missing field `")
+            .and_then(|field| field.strip_suffix('`'));
+
+        match missing {
+            Some(missing) => Self {
+                source,
+                field: Some(match path {
+                    Some(path) => format!("{path}.{missing}"),
+                    None => missing.to_string(),
+                }),
+                reason: "missing field".to_string(),
+                status,
+            },
+            None => Self {
+                source,
+                field: path,
+                reason,
+                status,
+            },
+        }
+    }
+
+    /// Returns the part of the request that the value is extracted from
+    pub fn get_source(&self) -> ExtractSource {
+        self.source
+    }
+
+    /// Returns the field that failed, e.g. `page` or `address.city`. It is `None` when the error
+    /// is not about a single field, like a body that is not JSON.
+    pub fn get_field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
+
+    /// Returns why the field failed
+    pub fn get_reason(&self) -> &str {
+        &self.reason
+    }
+
+    /// Returns the status of the response for the error, `400 Bad Request` or
+    /// `422 Unprocessable Entity`
+    pub fn get_status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Returns the error as a JSON object with the `error`, `source`, `field` and `reason` keys
+    pub fn to_json(&self) -> JsonValue {
+        json!({
+            "error": self.status.get_message(),
+            "source": self.source.to_string(),
+            "field": self.field,
+            "reason": self.reason,
+        })
+    }
+
+    /// Sets the status of the response and writes the error to its body as JSON
+    pub fn respond(&self, response: &mut Response) {
+        response.status(self.status).body_json(self.to_json());
+    }
+}
+
+impl Display for ExtractError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        match &self.field {
+            Some(field) => write!(
+                f,
+                "Invalid {} field `{field}`: {}",
+                self.source, self.reason
+            ),
+            None => write!(f, "Invalid {}: {}", self.source, self.reason),
+        }
+    }
+}
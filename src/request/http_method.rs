@@ -13,9 +13,10 @@ use std::fmt::{Display, Error, Formatter, Result as fResult};
 /// - PATCH
 /// - DELETE
 ///
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub enum HttpMethod {
     /// GET method is used to request data from a specified resource
+    #[default]
     GET,
     /// POST method is used to submit data to be processed to a specified resource
     POST,
@@ -32,17 +33,20 @@ pub enum HttpMethod {
 }
 
 impl HttpMethod {
+    /// Every supported method
+    pub const ALL: &'static [HttpMethod] = &[
+        HttpMethod::GET,
+        HttpMethod::POST,
+        HttpMethod::PUT,
+        HttpMethod::PATCH,
+        HttpMethod::DELETE,
+    ];
+
     pub(crate) fn is_valid(method: &str) -> bool {
         matches!(method, "GET" | "POST" | "PUT" | "PATCH" | "DELETE")
     }
 }
 
-impl Default for HttpMethod {
-    fn default() -> Self {
-        Self::GET
-    }
-}
-
 impl Display for HttpMethod {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         match self {
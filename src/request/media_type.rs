@@ -0,0 +1,229 @@
+use std::fmt::{Display, Formatter, Result as fResult};
+
+use super::Request;
+
+/// Represents a media type, e.g. `application/json; charset=utf-8`
+///
+/// The type, the subtype and the names of the parameters are case-insensitive, so they are kept
+/// in lowercase. The values of the parameters keep their case.
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::MediaType;
+///
+/// let media_type = MediaType::try_from("application/vnd.api+json; charset=UTF-8").unwrap();
+///
+/// assert_eq!(media_type.get_type(), "application");
+/// assert_eq!(media_type.get_subtype(), "vnd.api+json");
+/// assert_eq!(media_type.get_suffix(), Some("json"));
+/// assert_eq!(media_type.get_parameter("charset"), Some("UTF-8"));
+/// assert!(media_type.is_json());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaType {
+    main_type: String,
+    subtype: String,
+    parameters: Vec<(String, String)>,
+}
+
+impl MediaType {
+    /// Returns the type, e.g. `application` for `application/json`
+    pub fn get_type(&self) -> &str {
+        &self.main_type
+    }
+
+    /// Returns the subtype with its suffix, e.g. `vnd.api+json` for `application/vnd.api+json`
+    pub fn get_subtype(&self) -> &str {
+        &self.subtype
+    }
+
+    /// Returns the structured syntax suffix of the subtype, e.g. `json` for
+    /// `application/vnd.api+json`
+    pub fn get_suffix(&self) -> Option<&str> {
+        self.subtype.rsplit_once('+').map(|(_, suffix)| suffix)
+    }
+
+    /// Returns the parameters in order, with their names in lowercase
+    pub fn get_parameters(&self) -> &[(String, String)] {
+        &self.parameters
+    }
+
+    /// Returns the value of a parameter. The name is case-insensitive.
+    pub fn get_parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the `charset` parameter
+    pub fn get_charset(&self) -> Option<&str> {
+        self.get_parameter("charset")
+    }
+
+    /// Returns the type and the subtype without the parameters, e.g. `application/json`
+    pub fn essence(&self) -> String {
+        format!("{}/{}", self.main_type, self.subtype)
+    }
+
+    /// Checks if the media type is `application/json` or `application/*+json`
+    pub fn is_json(&self) -> bool {
+        self.main_type == "application"
+            && (self.subtype == "json" || self.get_suffix() == Some("json"))
+    }
+}
+
+impl TryFrom<&str> for MediaType {
+    type Error = ParseMediaTypeError;
+
+    /// Parses a media type, e.g. the value of a `Content-Type` header
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type or the subtype is missing or is not a valid token.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (essence, parameters) = value.split_once(';').unwrap_or((value, ""));
+        let (main_type, subtype) = essence
+            .trim()
+            .split_once('/')
+            .ok_or_else(|| ParseMediaTypeError(value.to_string()))?;
+
+        if !is_token(main_type) || !is_token(subtype) {
+            return Err(ParseMediaTypeError(value.to_string()));
+        }
+
+        Ok(Self {
+            main_type: main_type.to_lowercase(),
+            subtype: subtype.to_lowercase(),
+            parameters: parse_parameters(parameters)
+                .into_iter()
+                .map(|(key, value)| (key.to_lowercase(), value))
+                .collect(),
+        })
+    }
+}
+
+impl Display for MediaType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write!(f, "{}/{}", self.main_type, self.subtype)?;
+
+        for (key, value) in &self.parameters {
+            match is_token(value) {
+                true => write!(f, "; {key}={value}")?,
+                false => write!(
+                    f,
+                    "; {key}=\"{}\"",
+                    value.replace('\\', "\\\\").replace('"', "\\\"")
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Request {
+    /// Returns the media type of the `Content-Type` header. It is `None` if there is no
+    /// `Content-Type` header or if it is not a valid media type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Request, Response, StatusCode };
+    ///
+    /// fn post(request: &Request, response: &mut Response) {
+    ///   match request.get_content_type() {
+    ///     Some(media_type) if media_type.is_json() => {
+    ///       response.status(StatusCode::Ok);
+    ///     }
+    ///     _ => {
+    ///       response.status(StatusCode::UnsupportedMediaType);
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    pub fn get_content_type(&self) -> Option<MediaType> {
+        let content_type = self.parts.headers.get("content-type")?;
+
+        MediaType::try_from(content_type.as_str()).ok()
+    }
+}
+
+/// Parses the `; key=value` parameters of a header. Values can be quoted, and a quoted value can
+/// contain `;`. Parameters without a `=` or a name are skipped.
+pub(crate) fn parse_parameters(input: &str) -> Vec<(String, String)> {
+    let mut parameters = Vec::new();
+    let mut rest = input;
+
+    loop {
+        rest = rest.trim_start_matches([';', ' ', '\t']);
+
+        if rest.is_empty() {
+            return parameters;
+        }
+
+        let key_end = rest.find(['=', ';']).unwrap_or(rest.len());
+        let key = rest[..key_end].trim();
+
+        let Some(after_key) = rest[key_end..].strip_prefix('=') else {
+            rest = &rest[key_end..];
+            continue;
+        };
+        let after_key = after_key.trim_start();
+
+        let (value, after_value) = match after_key.strip_prefix('"') {
+            Some(quoted) => {
+                let mut value = String::new();
+                let mut chars = quoted.char_indices();
+                let mut end = quoted.len();
+
+                while let Some((index, char)) = chars.next() {
+                    match char {
+                        '\\' => value.extend(chars.next().map(|(_, escaped)| escaped)),
+                        '"' => {
+                            end = index + 1;
+                            break;
+                        }
+                        _ => value.push(char),
+                    }
+                }
+
+                (value, &quoted[end..])
+            }
+            None => {
+                let end = after_key.find(';').unwrap_or(after_key.len());
+                (after_key[..end].trim().to_string(), &after_key[end..])
+            }
+        };
+
+        if !key.is_empty() {
+            parameters.push((key.to_string(), value));
+        }
+
+        rest = match after_value.find(';') {
+            Some(index) => &after_value[index..],
+            None => "",
+        };
+    }
+}
+
+/// Checks if the value is a token of RFC 9110, which can be written without quotes.
+pub(crate) fn is_token(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(char))
+}
+
+#[derive(Debug)]
+/// Error for parsing a media type
+///
+/// This error is returned when the media type has no valid type or subtype
+pub struct ParseMediaTypeError(String);
+
+impl Display for ParseMediaTypeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write!(f, "Invalid media type: {}", self.0)
+    }
+}
@@ -0,0 +1,343 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::Path,
+    sync::Arc,
+};
+
+use tempfile::NamedTempFile;
+use tokio::{fs::File, io::AsyncWriteExt, task};
+
+use super::{body::BodyLimits, media_type::parse_parameters, parser::BodyReader, uri::percent_decode};
+
+/// The maximum size of the headers of a part
+const MAX_PART_HEAD: usize = 8 * 1024;
+
+/// Represents a `multipart/form-data` body
+///
+/// The text fields are kept in memory and the files are written to temporary files, which are
+/// deleted when the request is dropped.
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::{ Request, Response, StatusCode, request::RequestBody };
+///
+/// fn upload(request: &Request, response: &mut Response) {
+///   let RequestBody::Multipart(multipart) = request.get_body() else {
+///     response.status(StatusCode::BadRequest);
+///     return;
+///   };
+///
+///   let title = multipart.get_field("title");
+///
+///   if let Some(avatar) = multipart.get_file("avatar") {
+///     avatar.persist("uploads/avatar.png").unwrap();
+///   }
+///
+///   response.status(StatusCode::Created);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Multipart {
+    fields: HashMap<String, Vec<String>>,
+    files: HashMap<String, Vec<UploadedFile>>,
+}
+
+impl Multipart {
+    /// Returns the values of every text field in order
+    pub fn get_fields(&self) -> &HashMap<String, Vec<String>> {
+        &self.fields
+    }
+
+    /// Returns the first value of a text field
+    pub fn get_field(&self, name: &str) -> Option<&String> {
+        self.fields.get(name).and_then(|values| values.first())
+    }
+
+    /// Returns the files of every file field in order
+    pub fn get_files(&self) -> &HashMap<String, Vec<UploadedFile>> {
+        &self.files
+    }
+
+    /// Returns the first file of a file field
+    pub fn get_file(&self, name: &str) -> Option<&UploadedFile> {
+        self.files.get(name).and_then(|files| files.first())
+    }
+
+    /// Reads the parts of the body. A part with a `filename` is a file and the others are text
+    /// fields.
+    pub(crate) async fn parse(
+        reader: &mut BodyReader<'_>,
+        boundary: &str,
+        limits: &BodyLimits,
+    ) -> Result<Self, Error> {
+        let delimiter = format!("\r\n--{boundary}").into_bytes();
+        let mut multipart = Multipart::default();
+
+        // The first boundary is not preceded by a line break
+        let mut buffer = b"\r\n".to_vec();
+
+        loop {
+            match find(&buffer, &delimiter) {
+                Some(index) => {
+                    buffer.drain(..index + delimiter.len());
+                    break;
+                }
+                None => {
+                    buffer.drain(..buffer.len().saturating_sub(delimiter.len()));
+                    fill(reader, &mut buffer).await?;
+                }
+            }
+        }
+
+        loop {
+            while buffer.len() < 2 {
+                fill(reader, &mut buffer).await?;
+            }
+
+            if buffer.starts_with(b"--") {
+                return Ok(multipart);
+            }
+
+            let head_end = loop {
+                if let Some(index) = find(&buffer, b"\r\n\r\n") {
+                    break index;
+                }
+                if buffer.len() > MAX_PART_HEAD {
+                    return Err(invalid("Headers of a multipart part are too large"));
+                }
+
+                fill(reader, &mut buffer).await?;
+            };
+
+            let head = String::from_utf8(buffer[..head_end].to_vec())
+                .map_err(|_| invalid("Headers of a multipart part are not valid UTF-8"))?;
+            let part = PartHead::parse(&head)?;
+
+            buffer.drain(..head_end + 4);
+
+            let (mut sink, max_size) = match part.file_name {
+                Some(_) => (Sink::temp_file().await?, limits.get_max_file_size()),
+                None => (Sink::Memory(Vec::new()), limits.get_max_field_size()),
+            };
+
+            loop {
+                if let Some(index) = find(&buffer, &delimiter) {
+                    sink.write(&buffer[..index], max_size).await?;
+                    buffer.drain(..index + delimiter.len());
+                    break;
+                }
+
+                let complete = buffer.len().saturating_sub(delimiter.len() - 1);
+                sink.write(&buffer[..complete], max_size).await?;
+                buffer.drain(..complete);
+
+                fill(reader, &mut buffer).await?;
+            }
+
+            match sink {
+                Sink::File(file, mut writer, size) => {
+                    writer.flush().await?;
+
+                    multipart
+                        .files
+                        .entry(part.name)
+                        .or_default()
+                        .push(UploadedFile {
+                            file_name: part.file_name.unwrap_or_default(),
+                            content_type: part.content_type,
+                            size,
+                            file: Arc::new(file),
+                        });
+                }
+                Sink::Memory(bytes) => {
+                    let value = String::from_utf8(bytes)
+                        .map_err(|_| invalid("A multipart field is not valid UTF-8"))?;
+
+                    multipart.fields.entry(part.name).or_default().push(value);
+                }
+            }
+        }
+    }
+}
+
+/// Represents a file of a `multipart/form-data` body
+///
+/// The content of the file is in a temporary file, which is deleted when the request and all of
+/// its clones are dropped. [UploadedFile::persist] copies it to a permanent path.
+#[derive(Debug, Clone)]
+pub struct UploadedFile {
+    file_name: String,
+    content_type: Option<String>,
+    size: u64,
+    file: Arc<NamedTempFile>,
+}
+
+impl UploadedFile {
+    /// Returns the name of the file on the client, as it is sent in its `Content-Disposition`
+    ///
+    /// The name is not sanitized, so it should not be used as a path as it is.
+    pub fn get_file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// Returns the `Content-Type` of the part, if it has one
+    pub fn get_content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Returns the size of the file in bytes
+    pub fn get_size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the path of the temporary file
+    pub fn get_path(&self) -> &Path {
+        self.file.path()
+    }
+
+    /// Reads the content of the file into memory
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the temporary file cannot be read.
+    pub fn read(&self) -> Result<Vec<u8>, Error> {
+        fs::read(self.get_path())
+    }
+
+    /// Copies the file to the path
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be copied to the path.
+    pub fn persist(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        fs::copy(self.get_path(), path).map(|_| ())
+    }
+}
+
+impl PartialEq for UploadedFile {
+    fn eq(&self, other: &Self) -> bool {
+        self.get_path() == other.get_path()
+    }
+}
+
+impl Eq for UploadedFile {}
+
+/// The headers of a part that are used to read it
+struct PartHead {
+    name: String,
+    file_name: Option<String>,
+    content_type: Option<String>,
+}
+
+impl PartHead {
+    fn parse(head: &str) -> Result<Self, Error> {
+        let mut disposition = None;
+        let mut content_type = None;
+
+        for line in head.split("\r\n").filter(|line| !line.is_empty()) {
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(invalid("Invalid header in a multipart part"));
+            };
+
+            match key.trim().to_lowercase().as_str() {
+                "content-disposition" => disposition = Some(value.trim()),
+                "content-type" => content_type = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+
+        let disposition = disposition
+            .ok_or_else(|| invalid("A multipart part has no Content-Disposition header"))?;
+        let (kind, parameters) = disposition.split_once(';').unwrap_or((disposition, ""));
+
+        if !kind.trim().eq_ignore_ascii_case("form-data") {
+            return Err(invalid("A multipart part is not form-data"));
+        }
+
+        let parameters = parse_parameters(parameters);
+        let get = |key: &str| {
+            parameters
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(key))
+                .map(|(_, value)| value.clone())
+        };
+
+        // `filename*` holds the name as `UTF-8''<percent-encoded name>`
+        let extended_file_name = get("filename*").and_then(|value| {
+            let (charset, name) = value.split_once("''")?;
+            charset.eq_ignore_ascii_case("utf-8").then_some(())?;
+            percent_decode(name, false).ok()
+        });
+
+        Ok(Self {
+            name: get("name").ok_or_else(|| invalid("A multipart part has no name"))?,
+            file_name: extended_file_name.or_else(|| get("filename")),
+            content_type,
+        })
+    }
+}
+
+/// Where the content of a part is written to. A file is written through an asynchronous handle
+/// of the temporary file, so that the connection does not block the runtime.
+enum Sink {
+    Memory(Vec<u8>),
+    File(NamedTempFile, File, u64),
+}
+
+impl Sink {
+    /// Creates a temporary file on the blocking thread pool
+    async fn temp_file() -> Result<Self, Error> {
+        let file = task::spawn_blocking(NamedTempFile::new)
+            .await
+            .map_err(Error::other)??;
+        let writer = File::from_std(file.as_file().try_clone()?);
+
+        Ok(Sink::File(file, writer, 0))
+    }
+
+    async fn write(&mut self, bytes: &[u8], max_size: usize) -> Result<(), Error> {
+        let size = match self {
+            Sink::Memory(memory) => memory.len(),
+            Sink::File(_, _, size) => *size as usize,
+        };
+
+        if size + bytes.len() > max_size {
+            return Err(Error::new(
+                ErrorKind::FileTooLarge,
+                "A multipart part is larger than the limit",
+            ));
+        }
+
+        match self {
+            Sink::Memory(memory) => memory.extend_from_slice(bytes),
+            Sink::File(_, writer, size) => {
+                writer.write_all(bytes).await?;
+                *size += bytes.len() as u64;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the next chunk of the body into the buffer. The body must not end before the closing
+/// boundary.
+async fn fill(reader: &mut BodyReader<'_>, buffer: &mut Vec<u8>) -> Result<(), Error> {
+    match reader.read_chunk(buffer).await? {
+        true => Ok(()),
+        false => Err(invalid("Multipart body ended before its closing boundary")),
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
@@ -1,172 +1,318 @@
 use core::str;
 use std::{
     collections::HashMap,
-    io::{Error, ErrorKind},};
+    io::{Error, ErrorKind},
+    sync::Arc,
+};
 
 use tokio::{io::AsyncReadExt, net::TcpStream};
 
-use super::{request_line::RequestLine, ParseHttpRequestError, Request, RequestBody};
+use crate::headers::HeaderMap;
 
+use super::{
+    body::BodyLimits,
+    media_type::MediaType,
+    multipart::Multipart,
+    request_line::RequestLine,
+    uri::{parse_query, DecodeError},
+    Parts, Request, RequestBody,
+};
+
+/// The maximum number of headers
 const MAX_HEADER: usize = 100;
+/// The maximum size of the request line and the headers
+const MAX_HEAD_SIZE: usize = 16 * 1024;
+const CHUNK_SIZE: usize = 8 * 1024;
 
 impl Request {
     /// Parses a TcpStream into Request
-    pub(crate) async fn parse(stream: &mut TcpStream) -> Result<Self, Error> {
+    pub(crate) async fn parse(stream: &mut TcpStream, limits: &BodyLimits) -> Result<Self, Error> {
         let peer_addr = stream.peer_addr()?;
-        let buffer: &mut [u8] = &mut [0; 1024];
+        let head = Self::read_head(stream).await?;
+
+        let (http_request, body) = Self::split_request(&head);
 
-        let stream_length = stream.read(buffer).await?;
+        if http_request.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "Empty request"));
+        }
 
-        let (http_request, body) = Self::split_request(&buffer[..stream_length]);
+        let request_line = match str::from_utf8(http_request[0]) {
+            Ok(line) if line.is_ascii() => RequestLine::try_from(line),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Request line is not valid ASCII",
+                ));
+            }
+        };
 
-        let request_line = RequestLine::try_from(http_request[0]);
+        let request_line = match request_line {
+            Ok(request_line) => request_line,
+            Err(_) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Error while parsing request line".to_string(),
+                ));
+            }
+        };
 
-        if request_line.is_err() {
+        if request_line.get_version() != "HTTP/1.1" {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
-                "Error while parsing request line".to_string(),
+                "Invalid HTTP version".to_string(),
             ));
         }
 
-        let request_line = request_line.unwrap();
-
-        if request_line.get_version() != "HTTP/1.1" {
+        if http_request.len() - 1 > MAX_HEADER {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
-                "Invalid HTTP version".to_string(),
+                format!("Request has more than {MAX_HEADER} headers"),
             ));
         }
 
-        let headers: HashMap<String, String> = http_request
+        let headers = http_request
             .iter()
             .skip(1)
-            .take(MAX_HEADER)
-            .filter_map(Request::header_parser())
-            .collect();
+            .map(|line| Self::parse_header(line))
+            .collect::<Result<HeaderMap, Error>>()?;
 
-        let content_length = Self::parse_length(&http_request).unwrap_or(0);
+        let content_length = Self::parse_length(&headers)?;
 
-        let queries = Self::parse_queries(&request_line.get_path_array());
+        let queries = match Self::parse_queries(request_line.get_query()) {
+            Ok(queries) => queries,
+            Err(err) => return Err(Error::new(ErrorKind::InvalidInput, err.to_string())),
+        };
 
-        if content_length == 0 {
-            return Ok(Request {
-                request: RequestLine::try_from(http_request[0]).unwrap(),
-                headers,
-                queries,
-                peer_addr,
-                params: HashMap::new(),
-                body: RequestBody::None,
-            });
+        if content_length > limits.get_max_body_size() {
+            return Err(Error::new(
+                ErrorKind::FileTooLarge,
+                "Request body is larger than the limit",
+            ));
         }
 
-        let body: RequestBody = Self::parse_body(body, &headers)?;
+        // The type is only needed to parse the body, so a request without one is not rejected
+        // for a type that can not be parsed
+        let content_type = match headers.get("content-type") {
+            Some(_) if content_length == 0 => None,
+            Some(content_type) => match MediaType::try_from(content_type.as_str()) {
+                Ok(media_type) => Some(media_type),
+                Err(err) => return Err(Error::new(ErrorKind::InvalidInput, err.to_string())),
+            },
+            None => None,
+        };
 
-        Ok(Request {
-            request: request_line,
-            headers,
-            queries,
-            params: HashMap::new(),
-            peer_addr,
-            body,
-        })
-    }
+        let mut reader = BodyReader::new(stream, body, content_length);
 
-    pub(crate) fn parse_queries(path_array: &Vec<String>) -> HashMap<String, String> {
-        let path_last = path_array.last();
+        let body = match (content_length, &content_type) {
+            (0, _) => RequestBody::None,
+            (_, Some(media_type))
+                if media_type.get_type() == "multipart"
+                    && media_type.get_subtype() == "form-data" =>
+            {
+                let boundary = media_type.get_parameter("boundary").ok_or_else(|| {
+                    Error::new(ErrorKind::InvalidInput, "Multipart body has no boundary")
+                })?;
 
-        return match path_last {
-            Some(last) if !last.is_empty() && last.contains('?') => {
-                Self::parse_query_uri(last).unwrap_or(HashMap::new())
+                RequestBody::Multipart(Multipart::parse(&mut reader, boundary, limits).await?)
             }
-            _ => HashMap::new(),
+            _ => RequestBody::parse(&reader.read_to_end().await?, content_type.as_ref())?,
         };
-    }
 
-    fn parse_query_uri(uri_last: &str) -> Result<HashMap<String, String>, Error> {
-        let query_params = uri_last.split('?').skip(1).collect::<Vec<&str>>();
+        let mut request = Request {
+            parts: Arc::new(Parts {
+                request: request_line,
+                headers,
+                queries: HashMap::new(),
+                query_values: HashMap::new(),
+                peer_addr,
+                body,
+            }),
+            params: HashMap::new(),
+        };
 
-        if query_params.len() != 1 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Invalid query usage".to_string(),
-            ));
-        }
+        request.set_queries(queries);
 
-        return Ok(query_params[0]
-            .split('&')
-            .fold(HashMap::new(), |mut queries, kvp| {
-                if let Some((k, v)) = kvp.split_once('=') {
-                    queries.insert(k.to_string(), v.to_string());
-                }
+        Ok(request)
+    }
 
-                return queries;
-            }));
+    /// Returns the decoded key-value pairs of the query string.
+    pub(crate) fn parse_queries(query: Option<&str>) -> Result<Vec<(String, String)>, DecodeError> {
+        match query {
+            Some(query) => parse_query(query),
+            None => Ok(Vec::new()),
+        }
     }
 
-    fn header_parser() -> impl Fn(&&str) -> Option<(String, String)> {
-        |line: &&str| {
-            let header_line: Vec<&str> = line.split(':').collect();
+    /// Parses a header line into its name and its value.
+    ///
+    /// The name must be a token. The value is opaque bytes, so it is decoded as ISO-8859-1, which
+    /// maps every byte to a character and keeps ASCII values as they are.
+    fn parse_header(line: &[u8]) -> Result<(String, String), Error> {
+        let invalid = || {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid header: {}", String::from_utf8_lossy(line)),
+            )
+        };
 
-            if header_line.len() == 2 {
-                let key = header_line[0].trim().to_lowercase().to_string();
-                let value = header_line[1].trim().to_string();
+        let colon = line
+            .iter()
+            .position(|&byte| byte == b':')
+            .ok_or_else(invalid)?;
+        let (name, value) = (&line[..colon], &line[colon + 1..]);
 
-                Some((key, value))
-            } else {
-                None
-            }
+        if name.is_empty() || !name.iter().all(|&byte| is_token_byte(byte)) {
+            return Err(invalid());
         }
+
+        let value = value.trim_ascii();
+
+        if value
+            .iter()
+            .any(|&byte| matches!(byte, b'\r' | b'\n' | b'\0'))
+        {
+            return Err(invalid());
+        }
+
+        Ok((
+            String::from_utf8_lossy(name).into_owned(),
+            value.iter().map(|&byte| char::from(byte)).collect(),
+        ))
     }
 
-    fn parse_length(headers: &[&str]) -> Option<usize> {
-        for line in headers.iter() {
-            if line.starts_with("Content-Length") {
-                match line.find(':') {
-                    Some(index) => {
-                        return Some(line[index + 1..].trim().parse::<usize>().unwrap_or(0));
-                    }
-                    None => {
-                        return None;
-                    }
-                }
+    /// Returns the `Content-Length` of the request, which is `0` without the header. Repeated
+    /// headers must have the same value.
+    fn parse_length(headers: &HeaderMap) -> Result<usize, Error> {
+        let mut lengths = headers.get_all("content-length").map(|length| {
+            length.parse::<usize>().map_err(|_| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Invalid Content-Length: {length}"),
+                )
+            })
+        });
+
+        let Some(length) = lengths.next().transpose()? else {
+            return Ok(0);
+        };
+
+        for other in lengths {
+            if other? != length {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Conflicting Content-Length headers",
+                ));
             }
         }
-        return None;
+
+        Ok(length)
     }
 
-    fn parse_body(body: &[u8], headers: &HashMap<String, String>) -> Result<RequestBody, Error> {
-        if body.is_empty() {
-            return Err(Error::new(std::io::ErrorKind::NotFound, "Body is empty."));
-        }
+    /// Reads the stream until the end of the headers.
+    async fn read_head(stream: &mut TcpStream) -> Result<Vec<u8>, Error> {
+        let mut head = Vec::new();
+        let mut chunk = [0; CHUNK_SIZE];
 
-        match headers.get("content-type") {
-            Some(content_type) => {
-                return RequestBody::parse(body, content_type);
-            }
-            None => {
+        while !head.windows(4).any(|window| window == b"\r\n\r\n")
+            && !head.windows(2).any(|window| window == b"\n\n")
+        {
+            if head.len() > MAX_HEAD_SIZE {
                 return Err(Error::new(
-                    ErrorKind::NotFound,
-                    ParseHttpRequestError.to_string(),
+                    ErrorKind::InvalidInput,
+                    "Request headers are too large",
                 ));
             }
+
+            match stream.read(&mut chunk).await? {
+                0 => break,
+                read => head.extend_from_slice(&chunk[..read]),
+            }
         }
-    }
 
-    fn split_request(vec: &[u8]) -> (Vec<&str>, &[u8]) {
-        let mut split = vec.split(|&x| x == b'\n');
+        Ok(head)
+    }
 
+    /// Splits the request into its lines until the empty line, without the line endings, and
+    /// the body after the empty line.
+    fn split_request(vec: &[u8]) -> (Vec<&[u8]>, &[u8]) {
         let mut http_request = Vec::new();
+        let mut rest = vec;
+
+        while !rest.is_empty() {
+            let end = rest.iter().position(|&x| x == b'\n').unwrap_or(rest.len());
+            let line = &rest[..end];
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+            rest = rest.get(end + 1..).unwrap_or(&[]);
 
-        while let Some(line) = split.next() {
             if line.is_empty() {
                 break;
             }
 
-            http_request.push(str::from_utf8(line).unwrap());
+            http_request.push(line);
+        }
+
+        (http_request, rest)
+    }
+}
+
+/// Checks if the byte is a `tchar` of RFC 9110, which header names are made of.
+fn is_token_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte)
+}
+
+/// Reads the body of a request from the stream, after the part of it that is read with the
+/// headers. It does not read past the `Content-Length` of the request.
+pub(crate) struct BodyReader<'a> {
+    stream: &'a mut TcpStream,
+    buffered: Vec<u8>,
+    remaining: usize,
+}
+
+impl<'a> BodyReader<'a> {
+    fn new(stream: &'a mut TcpStream, buffered: &[u8], content_length: usize) -> Self {
+        let buffered = &buffered[..buffered.len().min(content_length)];
+
+        Self {
+            stream,
+            buffered: buffered.to_vec(),
+            remaining: content_length - buffered.len(),
         }
+    }
+
+    /// Appends the next chunk of the body to the buffer. Returns `false` if the whole body is
+    /// read.
+    pub(crate) async fn read_chunk(&mut self, buffer: &mut Vec<u8>) -> Result<bool, Error> {
+        if !self.buffered.is_empty() {
+            buffer.append(&mut self.buffered);
+            return Ok(true);
+        }
+
+        if self.remaining == 0 {
+            return Ok(false);
+        }
+
+        let mut chunk = [0; CHUNK_SIZE];
+        let length = self.remaining.min(CHUNK_SIZE);
+
+        match self.stream.read(&mut chunk[..length]).await? {
+            0 => Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "Request body is shorter than its Content-Length",
+            )),
+            read => {
+                self.remaining -= read;
+                buffer.extend_from_slice(&chunk[..read]);
+                Ok(true)
+            }
+        }
+    }
+
+    async fn read_to_end(mut self) -> Result<Vec<u8>, Error> {
+        let mut body = Vec::new();
 
-        let body = split.next().unwrap_or(&[]);
+        while self.read_chunk(&mut body).await? {}
 
-        (http_request, body)
+        Ok(body)
     }
 }
@@ -1,11 +1,11 @@
 use std::fmt::{Display, Formatter, Result as fResult};
 
-use super::http_method::HttpMethod;
+use super::{http_method::HttpMethod, uri::Uri};
 
 #[derive(Clone)]
 pub(crate) struct RequestLine {
     method: HttpMethod,
-    uri: String,
+    uri: Uri,
     version: String,
     path_array: Vec<String>,
 }
@@ -16,27 +16,16 @@ impl RequestLine {
         uri: &str,
         version: &str,
     ) -> Result<Self, ParseRequestLineError> {
-        match HttpMethod::try_from(method) {
-            Ok(method) => Ok(Self {
-                method,
-                uri: uri.to_string(),
-                version: version.to_string(),
-                path_array: Self::uri_parser(uri),
-            }),
-            Err(_) => {
-                return Err(ParseRequestLineError);
-            }
-        }
-    }
-
-    fn uri_parser(uri: &str) -> Vec<String> {
-        if uri.starts_with('/') {
-            uri.split('/').skip(1).map(|str| str.to_string()).collect()
-        } else if uri.starts_with("http") {
-            uri.split('/').skip(3).map(|str| str.to_string()).collect()
-        } else {
-            uri.split('/').skip(1).map(|str| str.to_string()).collect()
-        }
+        let method = HttpMethod::try_from(method).map_err(|_| ParseRequestLineError)?;
+        let uri = Uri::parse(uri).map_err(|_| ParseRequestLineError)?;
+        let path_array = uri.get_segments().map_err(|_| ParseRequestLineError)?;
+
+        Ok(Self {
+            method,
+            uri,
+            version: version.to_string(),
+            path_array,
+        })
     }
 
     pub(super) fn get_method(&self) -> &HttpMethod {
@@ -51,17 +40,37 @@ impl RequestLine {
         &self.version
     }
 
+    pub(super) fn get_authority(&self) -> Option<&str> {
+        self.uri.get_authority()
+    }
+
     pub(super) fn get_path(&self) -> &str {
-        &self.uri.as_str()
+        self.uri.get_path()
+    }
+
+    pub(super) fn get_query(&self) -> Option<&str> {
+        self.uri.get_query()
+    }
+
+    pub(super) fn get_fragment(&self) -> Option<&str> {
+        self.uri.get_fragment()
     }
 
     pub(crate) fn set_method(&mut self, method: HttpMethod) {
         self.method = method;
     }
 
+    /// Sets the request target.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the target is not in origin-form, absolute-form or `*`, or if its path has an
+    /// invalid percent-encoding.
     pub(crate) fn set_uri(&mut self, uri: &str) {
-        self.uri = String::from(uri);
-        self.path_array = Self::uri_parser(uri);
+        let uri = Uri::parse(uri).unwrap_or_else(|err| panic!("{err}"));
+
+        self.path_array = uri.get_segments().unwrap_or_else(|err| panic!("{err}"));
+        self.uri = uri;
     }
 
     pub(crate) fn set_version(&mut self, version: &str) {
@@ -0,0 +1,253 @@
+use std::{
+    borrow::Cow,
+    fmt::{Display, Formatter, Result as fResult},
+};
+
+use super::charset::Charset;
+
+/// The target of a request, split into its parts once when the request is parsed.
+///
+/// It can be in origin-form, `/users?page=2`, in absolute-form, `http://example.com/users`, or
+/// `*`. The parts are kept as they are in the target, without percent-decoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Uri {
+    target: String,
+    authority: Option<String>,
+    path: String,
+    query: Option<String>,
+    fragment: Option<String>,
+}
+
+impl Uri {
+    pub(crate) fn parse(target: &str) -> Result<Self, ParseUriError> {
+        let (rest, fragment) = match target.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment.to_string())),
+            None => (target, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query.to_string())),
+            None => (rest, None),
+        };
+
+        let (authority, path) = match rest.split_once("://") {
+            _ if rest == "*" => (None, rest),
+            Some((scheme, rest)) if is_valid_scheme(scheme) => {
+                let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+
+                match (authority.is_empty(), path.is_empty()) {
+                    (true, _) => return Err(ParseUriError(target.to_string())),
+                    (false, true) => (Some(authority.to_string()), "/"),
+                    (false, false) => (Some(authority.to_string()), path),
+                }
+            }
+            _ if rest.starts_with('/') => (None, rest),
+            _ => return Err(ParseUriError(target.to_string())),
+        };
+
+        Ok(Self {
+            target: target.to_string(),
+            authority,
+            path: path.to_string(),
+            query,
+            fragment,
+        })
+    }
+
+    /// Returns the authority of a target in absolute-form, e.g. `example.com:8080`
+    pub(crate) fn get_authority(&self) -> Option<&str> {
+        self.authority.as_deref()
+    }
+
+    pub(crate) fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    pub(crate) fn get_query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    pub(crate) fn get_fragment(&self) -> Option<&str> {
+        self.fragment.as_deref()
+    }
+
+    /// Returns the percent-decoded segments of the path
+    pub(crate) fn get_segments(&self) -> Result<Vec<String>, DecodeError> {
+        self.path
+            .split('/')
+            .skip(1)
+            .map(|segment| percent_decode(segment, false))
+            .collect()
+    }
+}
+
+impl Default for Uri {
+    fn default() -> Self {
+        Self {
+            target: "/".to_string(),
+            authority: None,
+            path: "/".to_string(),
+            query: None,
+            fragment: None,
+        }
+    }
+}
+
+impl Display for Uri {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write!(f, "{}", self.target)
+    }
+}
+
+fn is_valid_scheme(scheme: &str) -> bool {
+    scheme.starts_with(|char: char| char.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || matches!(char, '+' | '-' | '.'))
+}
+
+/// Decodes the `%XX` escapes of a path segment or, with `plus_as_space`, of a query component.
+///
+/// Fails if an escape is not followed by two hexadecimal digits or if the decoded bytes are not
+/// valid UTF-8.
+pub(crate) fn percent_decode(input: &str, plus_as_space: bool) -> Result<String, DecodeError> {
+    decode_component(input.as_bytes(), plus_as_space, Charset::Utf8)
+}
+
+/// Decodes the `%XX` escapes of the unreserved characters of a path, which are the ASCII letters,
+/// the digits, `-`, `.`, `_` and `~`, as they mean the same as the characters themselves. The
+/// other escapes are kept, so that an encoded `/` does not separate the segments.
+pub(crate) fn decode_unreserved(path: &str) -> Cow<'_, str> {
+    if !path.contains('%') {
+        return Cow::Borrowed(path);
+    }
+
+    let bytes = path.as_bytes();
+    let mut decoded = String::with_capacity(path.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let escape = (bytes[index] == b'%')
+            .then(|| bytes.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| Some((hex_value(hex[0])? << 4) | hex_value(hex[1])?))
+            .filter(|&byte| {
+                byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+            });
+
+        match escape {
+            Some(byte) => {
+                decoded.push(byte as char);
+                index += 3;
+            }
+            None => {
+                let char = path[index..].chars().next().unwrap_or_default();
+                decoded.push(char);
+                index += char.len_utf8();
+            }
+        }
+    }
+
+    Cow::Owned(decoded)
+}
+
+/// Parses a query string into its decoded key-value pairs in order. Keys without a value, like
+/// `debug` in `?debug&page=2`, get an empty value.
+pub(crate) fn parse_query(query: &str) -> Result<Vec<(String, String)>, DecodeError> {
+    parse_urlencoded(query.as_bytes(), Charset::Utf8)
+}
+
+/// Parses an `application/x-www-form-urlencoded` body, whose decoded bytes are in the charset,
+/// into its key-value pairs in order.
+pub(crate) fn parse_urlencoded(
+    input: &[u8],
+    charset: Charset,
+) -> Result<Vec<(String, String)>, DecodeError> {
+    input
+        .split(|&byte| byte == b'&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = match pair.iter().position(|&byte| byte == b'=') {
+                Some(index) => (&pair[..index], &pair[index + 1..]),
+                None => (pair, &[][..]),
+            };
+
+            Ok((
+                decode_component(key, true, charset)?,
+                decode_component(value, true, charset)?,
+            ))
+        })
+        .collect()
+}
+
+fn decode_component(
+    input: &[u8],
+    plus_as_space: bool,
+    charset: Charset,
+) -> Result<String, DecodeError> {
+    let mut decoded = Vec::with_capacity(input.len());
+    let mut index = 0;
+
+    while index < input.len() {
+        match input[index] {
+            b'%' => {
+                let escape = input
+                    .get(index + 1..index + 3)
+                    .and_then(|hex| Some((hex_value(hex[0])? << 4) | hex_value(hex[1])?));
+
+                match escape {
+                    Some(byte) => decoded.push(byte),
+                    None => return Err(DecodeError::InvalidEscape(lossy(input))),
+                }
+
+                index += 3;
+            }
+            b'+' if plus_as_space => {
+                decoded.push(b' ');
+                index += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+
+    charset
+        .decode(decoded)
+        .ok_or_else(|| DecodeError::InvalidUtf8(lossy(input)))
+}
+
+fn lossy(input: &[u8]) -> String {
+    String::from_utf8_lossy(input).into_owned()
+}
+
+fn hex_value(digit: u8) -> Option<u8> {
+    (digit as char).to_digit(16).map(|value| value as u8)
+}
+
+/// Represents an error that occurs when a request target is not in origin-form, absolute-form or
+/// `*`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ParseUriError(String);
+
+impl Display for ParseUriError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write!(f, "Invalid request target: {}", self.0)
+    }
+}
+
+/// Represents an error that occurs when percent-decoding a part of a URI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DecodeError {
+    InvalidEscape(String),
+    InvalidUtf8(String),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        match self {
+            DecodeError::InvalidEscape(input) => write!(f, "Invalid percent-encoding: {input}"),
+            DecodeError::InvalidUtf8(input) => write!(f, "Invalid UTF-8 in URI: {input}"),
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use self::status_code::StatusCode;
+use crate::headers::HeaderMap;
 use std::{
     collections::HashMap,
-    fmt::{Debug, Formatter, Result},
+    fmt::{Debug, Display, Formatter, Result},
 };
 
 pub use self::content_type::ContentType;
@@ -17,7 +18,7 @@ pub struct Response {
     debug_mode: bool,
     http_version: String,
     status_code: StatusCode,
-    headers: HashMap<String, String>,
+    headers: HeaderMap,
     locals: HashMap<String, String>,
     body: Vec<u8>,
 }
@@ -39,7 +40,10 @@ impl Response {
         self
     }
 
-    /// Adds headers to the response
+    /// Adds headers to the response. Each header replaces the values that it already has.
+    ///
+    /// It takes any collection of name and value pairs, e.g. a `HashMap`, a `Vec` of tuples or a
+    /// [HeaderMap].
     ///
     /// # Example
     ///
@@ -58,8 +62,13 @@ impl Response {
     ///     response.status(StatusCode::Ok).headers(headers);
     /// }
     /// ```
-    pub fn headers(&mut self, headers: HashMap<String, String>) -> &mut Self {
-        self.headers.extend(headers);
+    pub fn headers<K: AsRef<str>, V: AsRef<str>>(
+        &mut self,
+        headers: impl IntoIterator<Item = (K, V)>,
+    ) -> &mut Self {
+        for (key, value) in headers {
+            self.headers.insert(key.as_ref(), value.as_ref());
+        }
         self
     }
 
@@ -78,21 +87,18 @@ impl Response {
         let mut headers_string = String::new();
 
         if !self.body.is_empty() {
-            headers.insert("Content-Length".to_string(), self.body.len().to_string());
+            headers.insert("Content-Length", &self.body.len().to_string());
 
             if !headers.contains_key("Content-Type") {
                 eprintln!("Content-Type not found even though body is present");
 
-                headers.insert("Content-Type".to_string(), "text/plain".to_string());
+                headers.insert("Content-Type", "text/plain");
             }
         }
 
         if !headers.is_empty() {
-            let mut keys = headers.keys().collect::<Vec<&String>>();
-            keys.sort();
-
-            headers_string = keys.iter().fold(String::new(), |acc, key| {
-                format!("{acc}{key}: {value}\r\n", value = headers[*key])
+            headers_string = headers.iter().fold(String::new(), |acc, (key, value)| {
+                format!("{acc}{key}: {value}\r\n")
             });
         }
 
@@ -105,15 +111,15 @@ impl Response {
     }
 }
 
-impl ToString for Response {
-    fn to_string(&self) -> String {
-        let mut response_string = self.export_request_header();
+impl Display for Response {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.export_request_header())?;
 
         if !self.body.is_empty() {
-            response_string.push_str(&String::from_utf8_lossy(&self.body));
+            write!(f, "{}", String::from_utf8_lossy(&self.body))?;
         }
 
-        response_string
+        Ok(())
     }
 }
 
@@ -155,7 +161,7 @@ impl Default for Response {
             debug_mode: false,
             http_version: "HTTP/1.1".to_string(),
             status_code: StatusCode::NotFound,
-            headers: HashMap::new(),
+            headers: HeaderMap::new(),
             body: Vec::new(),
             locals: HashMap::new(),
         }
@@ -25,7 +25,7 @@ impl Response {
     /// ```
     pub fn body(&mut self, body: Vec<u8>, content_type: ContentType) -> &mut Self {
         self.headers
-            .insert(String::from("Content-Type"), content_type.to_string());
+            .insert("Content-Type", &content_type.to_string());
         self.body = body;
         self
     }
@@ -52,8 +52,7 @@ impl Response {
     /// }
     /// ```
     pub fn body_raw(&mut self, body: Vec<u8>, mime: &str) -> &mut Self {
-        self.headers
-            .insert(String::from("Content-Type"), mime.to_string());
+        self.headers.insert("Content-Type", mime);
         self.body = body;
         self
     }
@@ -3,7 +3,7 @@
 use std::fmt::{Debug, Display};
 
 /// Content type enum for response content type
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ContentType {
     // Text
     /// Represents the `text/plain` content type
@@ -1,9 +1,10 @@
 use std::fmt::{self, Display, Formatter};
 
 /// Represents the status code of an HTTP response
-#[derive(Eq, Hash, PartialEq, Debug, Clone, Copy)]
+#[derive(Eq, Hash, PartialEq, Debug, Clone, Copy, Default)]
 pub enum StatusCode {
     /// 200 OK
+    #[default]
     Ok = 200,
     /// 201 Created
     Created = 201,
@@ -11,6 +12,14 @@ pub enum StatusCode {
     Accepted = 202,
     /// 204 No Content
     NoContent = 204,
+    /// 206 Partial Content
+    PartialContent = 206,
+    /// 301 Moved Permanently
+    MovedPermanently = 301,
+    /// 304 Not Modified
+    NotModified = 304,
+    /// 308 Permanent Redirect
+    PermanentRedirect = 308,
     /// 400 Bad Request
     BadRequest = 400,
     /// 401 Unauthorized
@@ -21,14 +30,22 @@ pub enum StatusCode {
     NotFound = 404,
     /// 405 Method Not Allowed
     MethodNotAllowed = 405,
+    /// 406 Not Acceptable
+    NotAcceptable = 406,
     /// 408 Request Timeout
     RequestTimeout = 408,
     /// 411 Length Required
     LengthRequired = 411,
+    /// 413 Payload Too Large
+    PayloadTooLarge = 413,
     /// 415 Unsupported Media Type
     UnsupportedMediaType = 415,
+    /// 416 Range Not Satisfiable
+    RangeNotSatisfiable = 416,
     /// 418 I'm A Teapot
     IAmATeapot = 418,
+    /// 422 Unprocessable Entity
+    UnprocessableEntity = 422,
     /// 249 Too Many Requests
     TooManyRequests = 429,
     /// 500 Internal Server Error
@@ -44,21 +61,29 @@ pub enum StatusCode {
 }
 
 impl StatusCode {
-    pub(super) fn get_message(&self) -> &str {
+    pub(crate) fn get_message(&self) -> &str {
         match self {
             Self::Ok => "OK",
             Self::Created => "Created",
             Self::Accepted => "Accepted",
             Self::NoContent => "No Content",
+            Self::PartialContent => "Partial Content",
+            Self::MovedPermanently => "Moved Permanently",
+            Self::NotModified => "Not Modified",
+            Self::PermanentRedirect => "Permanent Redirect",
             Self::BadRequest => "Bad Request",
             Self::Unauthorized => "Unauthorized",
             Self::Forbidden => "Forbidden",
             Self::NotFound => "Not Found",
             Self::MethodNotAllowed => "Method Not Allowed",
+            Self::NotAcceptable => "Not Acceptable",
             Self::RequestTimeout => "Request Timeout",
             Self::LengthRequired => "Length Required",
+            Self::PayloadTooLarge => "Payload Too Large",
             Self::UnsupportedMediaType => "Unsupported Media Type",
+            Self::RangeNotSatisfiable => "Range Not Satisfiable",
             Self::IAmATeapot => "I'm A Teapot",
+            Self::UnprocessableEntity => "Unprocessable Entity",
             Self::TooManyRequests => "Too Many Requests",
             Self::InternalServerError => "Internal Server Error",
             Self::NotImplemented => "Not Implemented",
@@ -69,12 +94,6 @@ impl StatusCode {
     }
 }
 
-impl Default for StatusCode {
-    fn default() -> Self {
-        Self::Ok
-    }
-}
-
 impl TryFrom<&str> for StatusCode {
     type Error = ParseStatusCodeError;
     /// Converts a `string` to a `StatusCode`
@@ -178,15 +197,23 @@ impl TryFrom<&u16> for StatusCode {
             201 => Ok(Self::Created),
             202 => Ok(Self::Accepted),
             204 => Ok(Self::NoContent),
+            206 => Ok(Self::PartialContent),
+            301 => Ok(Self::MovedPermanently),
+            304 => Ok(Self::NotModified),
+            308 => Ok(Self::PermanentRedirect),
             400 => Ok(Self::BadRequest),
             401 => Ok(Self::Unauthorized),
             403 => Ok(Self::Forbidden),
             404 => Ok(Self::NotFound),
             405 => Ok(Self::MethodNotAllowed),
+            406 => Ok(Self::NotAcceptable),
             408 => Ok(Self::RequestTimeout),
             411 => Ok(Self::LengthRequired),
+            413 => Ok(Self::PayloadTooLarge),
             415 => Ok(Self::UnsupportedMediaType),
+            416 => Ok(Self::RangeNotSatisfiable),
             418 => Ok(Self::IAmATeapot),
+            422 => Ok(Self::UnprocessableEntity),
             429 => Ok(Self::TooManyRequests),
             500 => Ok(Self::InternalServerError),
             501 => Ok(Self::NotImplemented),
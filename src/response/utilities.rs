@@ -2,9 +2,7 @@
 //!
 //! This module contains utility functions for the response object.
 
-use std::collections::HashMap;
-
-use crate::{Response, StatusCode};
+use crate::{HeaderMap, Response, StatusCode};
 
 impl Response {
     /// Gets the headers of the response
@@ -22,11 +20,12 @@ impl Response {
     ///   }
     /// }
     /// ```
-    pub fn get_headers(&self) -> &HashMap<String, String> {
+    pub fn get_headers(&self) -> &HeaderMap {
         &self.headers
     }
 
-    /// Gets requested header from the response
+    /// Gets requested header from the response. The name is case-insensitive and the first value
+    /// is returned if the header is repeated.
     ///
     /// # Example
     ///
@@ -46,7 +45,7 @@ impl Response {
         self.headers.get(key)
     }
 
-    /// Adds a single header to the response
+    /// Adds a single header to the response. It replaces the values that the header already has.
     ///
     /// # Example
     ///
@@ -58,7 +57,26 @@ impl Response {
     /// }
     /// ```
     pub fn set_header(&mut self, key: &str, value: &str) -> &mut Self {
-        self.headers.insert(key.to_string(), value.to_string());
+        self.headers.insert(key, value);
+        self
+    }
+
+    /// Adds a value to a header of the response without replacing its other values, e.g. for
+    /// multiple `Set-Cookie` headers
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Response, StatusCode, Request };
+    ///
+    /// fn get(request: &Request, response: &mut Response) {
+    ///   response
+    ///     .append_header("Set-Cookie", "theme=dark")
+    ///     .append_header("Set-Cookie", "lang=en");
+    /// }
+    /// ```
+    pub fn append_header(&mut self, key: &str, value: &str) -> &mut Self {
+        self.headers.append(key, value);
         self
     }
 
@@ -171,23 +189,27 @@ impl Response {
 
     /// Returns true if status code is 4xx or 5xx.
     pub fn is_error(&self) -> bool {
-        match self.status_code {
-            StatusCode::BadRequest => true,
-            StatusCode::Unauthorized => true,
-            StatusCode::Forbidden => true,
-            StatusCode::NotFound => true,
-            StatusCode::MethodNotAllowed => true,
-            StatusCode::RequestTimeout => true,
-            StatusCode::LengthRequired => true,
-            StatusCode::UnsupportedMediaType => true,
-            StatusCode::IAmATeapot => true,
-            StatusCode::TooManyRequests => true,
-            StatusCode::InternalServerError => true,
-            StatusCode::NotImplemented => true,
-            StatusCode::ServiceUnavailable => true,
-            StatusCode::GatewayTimeout => true,
-            StatusCode::HttpVersionNotSupported => true,
-            _ => false,
-        }
+        matches!(
+            self.status_code,
+            StatusCode::BadRequest
+                | StatusCode::Unauthorized
+                | StatusCode::Forbidden
+                | StatusCode::NotFound
+                | StatusCode::MethodNotAllowed
+                | StatusCode::NotAcceptable
+                | StatusCode::RequestTimeout
+                | StatusCode::LengthRequired
+                | StatusCode::PayloadTooLarge
+                | StatusCode::UnsupportedMediaType
+                | StatusCode::RangeNotSatisfiable
+                | StatusCode::IAmATeapot
+                | StatusCode::UnprocessableEntity
+                | StatusCode::TooManyRequests
+                | StatusCode::InternalServerError
+                | StatusCode::NotImplemented
+                | StatusCode::ServiceUnavailable
+                | StatusCode::GatewayTimeout
+                | StatusCode::HttpVersionNotSupported
+        )
     }
 }
@@ -1,26 +1,41 @@
 use crate::{
+    middleware::{run_middlewares, Continuation},
+    request::uri::{decode_unreserved, percent_decode},
     server::route_handler::{HandlerResult, RouteHandler},
     HttpMethod, Middleware, Request, Response, StatusCode,
 };
 use endpoint::Endpoint;
-use regex::Regex;
-use std::{collections::HashMap, fmt::Display, iter::Peekable, slice::Iter};
+use guard::Guard;
+use radix::{Params, RadixTree};
+use std::{borrow::Cow, collections::HashMap, fmt::Display, sync::Arc};
 
+pub mod builder;
 pub mod endpoint;
+mod guard;
+pub mod host;
 pub mod methods;
+pub mod openapi;
+mod radix;
+pub mod resource;
+pub mod table;
+pub mod url;
 
 pub(crate) type Controller = fn(&Request, &mut Response);
-type RouterResult<'a> = Option<(&'a mut Endpoint, HashMap<String, String>)>;
-
-// TODO: Look at Radix Tree
 
 #[doc = include_str!("../docs/core/router.md")]
 #[derive(Debug)]
 pub struct Router {
     endpoints: Vec<Endpoint>,
     middlewares: Vec<Box<dyn Middleware>>,
-    subdirs: HashMap<String, Box<Router>>,
-    param_dir: Option<(String, Box<Router>)>,
+    subdirs: Vec<(String, Box<Router>)>,
+    param_dirs: Vec<(String, Box<Router>)>,
+    any_param: Option<Box<Router>>,
+    mounted: Vec<Router>,
+    fallback: Option<Controller>,
+    trailing_slash: Option<TrailingSlash>,
+    case_insensitive: Option<bool>,
+    last_endpoint: Option<(Vec<PathType>, usize)>,
+    tree: Option<Arc<RadixTree<Route>>>,
 }
 
 impl Router {
@@ -46,13 +61,32 @@ impl Router {
         Self {
             endpoints: Vec::new(),
             middlewares: Vec::new(),
-            subdirs: HashMap::new(),
-            param_dir: None,
+            subdirs: Vec::new(),
+            param_dirs: Vec::new(),
+            any_param: None,
+            mounted: Vec::new(),
+            fallback: None,
+            trailing_slash: None,
+            case_insensitive: None,
+            last_endpoint: None,
+            tree: None,
         }
     }
 
     /// Adds a subrouter to a router. It is useful for creating subdirectories.
     ///
+    /// If there is already a router at the path, or the path is `/`, the routers are merged. Their
+    /// endpoints are combined and their subdirectories are merged recursively. A router that has
+    /// its own middlewares, trailing slash policy or case insensitivity is kept apart instead, so
+    /// they apply only to its own routes. Middlewares added to the parent router at a path with
+    /// [Router::use_middleware_at] run for the routes of the subrouter under that path as well,
+    /// before the middlewares of the subrouter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path is invalid or the routers have conflicting routes. Use
+    /// [Router::try_use_router] to handle these errors.
+    ///
     /// # Example
     ///
     /// Create a 'POST' method for `/user/comments`
@@ -75,21 +109,103 @@ impl Router {
     ///
     /// server.use_handler(main_router);
     /// ```
+    ///
+    /// Merge feature routers that share a prefix, or mount one at the root
+    ///
+    /// ```rust
+    /// use krustie::{ Router, StatusCode };
+    ///
+    /// let mut main_router = Router::new();
+    /// let mut users_router = Router::new();
+    /// let mut admin_router = Router::new();
+    ///
+    /// users_router.get("/users", |req, res| {
+    ///   res.status(StatusCode::Ok);
+    /// });
+    ///
+    /// admin_router.delete("/users/:id", |req, res| {
+    ///   res.status(StatusCode::NoContent);
+    /// });
+    ///
+    /// main_router.use_router("/", users_router);
+    /// main_router.use_router("/", admin_router);
+    /// ```
     pub fn use_router(&mut self, path: &str, router: Router) {
-        let path = path.trim();
+        if let Err(err) = self.try_use_router(path, router) {
+            panic!("Error while adding router: {err}");
+        }
+    }
+
+    /// Adds a subrouter to a router and returns an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [RouterError] if the path has an invalid segment, a route of the subrouter
+    /// conflicts with an existing route, or a fallback of the subrouter is at the same path as an
+    /// existing fallback. The router is not modified when an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, RouterError, StatusCode, HttpMethod };
+    ///
+    /// let mut main_router = Router::new();
+    /// let mut user_router = Router::new();
+    ///
+    /// main_router.get("/user", |req, res| {
+    ///   res.status(StatusCode::Ok);
+    /// });
+    ///
+    /// user_router.get("/", |req, res| {
+    ///   res.status(StatusCode::Ok);
+    /// });
+    ///
+    /// assert_eq!(
+    ///   main_router.try_use_router("/user", user_router),
+    ///   Err(RouterError::DuplicateRoute { method: HttpMethod::GET, path: "/user".to_string() })
+    /// );
+    /// ```
+    pub fn try_use_router(&mut self, path: &str, router: Router) -> Result<(), RouterError> {
+        let path_types = Self::try_get_path_types(path.trim())?;
 
-        if path == "/" || path.is_empty() {
-            panic!("Route already exist.");
+        let mut routes = Vec::new();
+        router.collect_routes(&mut path_types.clone(), &mut routes);
+
+        for (route_path, method, guarded) in &routes {
+            self.check_route(route_path, method, *guarded)?;
         }
 
-        let path_types = Self::get_path_types(path);
-        let mut types_iter = path_types.into_iter().peekable();
+        let mut fallbacks = Vec::new();
+        router.collect_fallbacks(&mut path_types.clone(), &mut fallbacks);
+
+        for fallback_path in &fallbacks {
+            if let Some(existing) =
+                self.find_same(fallback_path, &|router| router.fallback.is_some())
+            {
+                return Err(RouterError::DuplicateFallback(existing));
+            }
+        }
 
-        Self::add_router(self, router, &mut types_iter);
+        for name in router.collect_names() {
+            if self.find_named(&name).is_some() {
+                return Err(RouterError::DuplicateName(name));
+            }
+        }
+
+        self.tree = None;
+        self.last_endpoint = None;
+        self.get_branch_or_insert(path_types).merge(router);
+
+        Ok(())
     }
 
     /// Adds an endpoint to a router.
     ///
+    /// # Panics
+    ///
+    /// Panics if the path is invalid or there is already an endpoint with the same method for the
+    /// path. Use [Router::try_use_endpoint] to handle these errors.
+    ///
     /// # Example
     ///
     /// Create a 'GET' method for `/user`
@@ -112,245 +228,1096 @@ impl Router {
     ///
     /// ```
     pub fn use_endpoint(&mut self, path: &str, endpoint: Endpoint) {
-        let path = path.trim();
+        if let Err(err) = self.try_use_endpoint(path, endpoint) {
+            panic!("Error while adding endpoint: {err}");
+        }
+    }
 
-        if path == "/" || path.is_empty() {
-            self.endpoints.push(endpoint);
-        } else {
-            let path_types = Self::get_path_types(path);
-            let mut types_iter = path_types.into_iter().peekable();
+    /// Adds an endpoint to a router and returns an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [RouterError] if
+    ///
+    /// - the path has an invalid segment,
+    /// - there is already an endpoint with the same method for the path,
+    /// - there is an endpoint with the same method for the same path but with different parameter
+    ///   names, e.g. `/users/:id` and `/users/:name`,
+    /// - the path has two parameters with the same name, e.g. `/users/:id/posts/:id`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, RouterError, StatusCode, Request, Response, HttpMethod, Endpoint };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// fn get(req: &Request, res: &mut Response) {
+    ///   res.status(StatusCode::Ok);
+    /// }
+    ///
+    /// assert!(router.try_use_endpoint("/users/:id", Endpoint::new(HttpMethod::GET, get)).is_ok());
+    ///
+    /// assert_eq!(
+    ///   router.try_use_endpoint("/users/:name", Endpoint::new(HttpMethod::GET, get)),
+    ///   Err(RouterError::ConflictingParameters {
+    ///     method: HttpMethod::GET,
+    ///     path: "/users/:name".to_string(),
+    ///     existing: "/users/:id".to_string(),
+    ///   })
+    /// );
+    ///
+    /// assert_eq!(
+    ///   router.try_use_endpoint("/users/{id}", Endpoint::new(HttpMethod::GET, get)),
+    ///   Err(RouterError::InvalidSegment("{id}".to_string()))
+    /// );
+    /// ```
+    pub fn try_use_endpoint(&mut self, path: &str, endpoint: Endpoint) -> Result<(), RouterError> {
+        let path_types = Self::try_get_path_types(path.trim())?;
 
-            Self::add_endpoint(self, endpoint, &mut types_iter)
+        self.add_endpoint(path_types, endpoint)
+    }
+
+    /// Adds an endpoint at the parsed path if it does not conflict with the existing routes.
+    fn add_endpoint(
+        &mut self,
+        path_types: Vec<PathType>,
+        endpoint: Endpoint,
+    ) -> Result<(), RouterError> {
+        let guarded = !endpoint.get_guards().is_empty();
+        self.check_route(&path_types, endpoint.get_method(), guarded)?;
+
+        self.tree = None;
+        let endpoints = &mut self.get_branch_or_insert(path_types.clone()).endpoints;
+        endpoints.push(endpoint);
+        self.last_endpoint = Some((path_types, endpoints.len() - 1));
+
+        Ok(())
+    }
+
+    /// Adds a middleware to the router.
+    ///
+    /// Middlewares of a router run in the order they are added, before the endpoint that matches
+    /// the request. They run for every route of the router, including the routes of the subrouters
+    /// mounted with [Router::use_router]. Middlewares of a parent router run before the
+    /// middlewares of its subrouters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, StatusCode, Middleware, Request, Response, HandlerResult };
+    ///
+    /// #[derive(Clone)]
+    /// struct Auth;
+    ///
+    /// impl Middleware for Auth {
+    ///   fn middleware(&mut self, req: &Request, res: &mut Response) -> HandlerResult {
+    ///     match req.get_header("authorization") {
+    ///       Some(_) => HandlerResult::Next,
+    ///       None => {
+    ///         res.status(StatusCode::Unauthorized);
+    ///         HandlerResult::End
+    ///       }
+    ///     }
+    ///   }
+    /// }
+    ///
+    /// let mut admin_router = Router::new();
+    ///
+    /// admin_router
+    ///   .use_middleware(Auth)
+    ///   .get("/stats", |req, res| {
+    ///     res.status(StatusCode::Ok);
+    ///   });
+    /// ```
+    pub fn use_middleware(&mut self, middleware: impl Middleware + 'static) -> &mut Self {
+        self.last_endpoint = None;
+        self.middlewares.push(Box::new(middleware));
+        self
+    }
+
+    /// Adds a middleware to the router at the path. The middleware runs only for the routes under
+    /// the path, including the routes of the subrouters mounted under it. Parameters of the path
+    /// match the parameters of the routes at the same position regardless of their names, so a
+    /// middleware at `/users/:id` runs for `/users/:user_id/posts` as well.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path has an invalid segment. Use [Router::try_use_middleware_at] to handle
+    /// this error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, StatusCode, Middleware, Request, Response, HandlerResult };
+    ///
+    /// #[derive(Clone)]
+    /// struct Logger;
+    ///
+    /// impl Middleware for Logger {
+    ///   fn middleware(&mut self, req: &Request, res: &mut Response) -> HandlerResult {
+    ///     println!("{} {}", req.get_method(), req.get_path());
+    ///     HandlerResult::Next
+    ///   }
+    /// }
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// router
+    ///   .use_middleware_at("/admin", Logger)
+    ///   .get("/admin/stats", |req, res| {
+    ///     res.status(StatusCode::Ok);
+    ///   });
+    /// ```
+    pub fn use_middleware_at(
+        &mut self,
+        path: &str,
+        middleware: impl Middleware + 'static,
+    ) -> &mut Self {
+        if let Err(err) = self.try_use_middleware_at(path, middleware) {
+            panic!("Error while adding middleware: {err}");
         }
+
+        self
     }
 
-    fn add_router<'a>(
-        router: &'a mut Router,
-        new_router: Router,
-        iter: &mut Peekable<std::vec::IntoIter<PathType>>,
+    /// Adds a middleware to the router at the path and returns an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [RouterError] if the path has an invalid segment. The router is not modified
+    /// when an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, RouterError, middlewares::GzipEncoder };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// assert!(router.try_use_middleware_at("/admin", GzipEncoder).is_ok());
+    ///
+    /// assert_eq!(
+    ///   router.try_use_middleware_at("/admin/{id}", GzipEncoder),
+    ///   Err(RouterError::InvalidSegment("{id}".to_string()))
+    /// );
+    /// ```
+    pub fn try_use_middleware_at(
+        &mut self,
+        path: &str,
+        middleware: impl Middleware + 'static,
+    ) -> Result<(), RouterError> {
+        let path_types = Self::try_get_path_types(path.trim())?;
+
+        self.tree = None;
+        self.last_endpoint = None;
+        self.get_scope_or_insert(path_types)
+            .middlewares
+            .push(Box::new(middleware));
+
+        Ok(())
+    }
+
+    /// Sets the fallback of the router. It is run instead of an endpoint when no route of the
+    /// router matches the request.
+    ///
+    /// Every router, including the subrouters, can have its own fallback. The fallback of the
+    /// deepest router on the path of the request is used, and the middlewares of the routers on
+    /// the way run before it. The status of the response is `404 Not Found` unless the fallback
+    /// changes it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, StatusCode, json::json, response::ContentType };
+    ///
+    /// let mut main_router = Router::new();
+    /// let mut api_router = Router::new();
+    ///
+    /// api_router.use_fallback(|req, res| {
+    ///   res.status(StatusCode::NotFound).body_json(json!({ "error": "Not Found" }));
+    /// });
+    ///
+    /// main_router
+    ///   .use_fallback(|req, res| {
+    ///     res.status(StatusCode::NotFound)
+    ///       .body(b"<h1>Not Found</h1>".to_vec(), ContentType::Html);
+    ///   })
+    ///   .use_router("/api", api_router);
+    /// ```
+    pub fn use_fallback(&mut self, controller: Controller) -> &mut Self {
+        self.last_endpoint = None;
+        self.fallback = Some(controller);
+        self
+    }
+
+    /// Sets how the router treats a trailing slash at the end of the request path.
+    ///
+    /// Routes are registered without a trailing slash, so `/users` is the canonical form of
+    /// `/users/`. By default the router is [TrailingSlash::Lenient] and both forms match the same
+    /// route. The policy applies to the routes of the router and of its subrouters, unless a
+    /// subrouter sets its own.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, StatusCode, TrailingSlash };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// router
+    ///   .trailing_slash(TrailingSlash::PermanentRedirect)
+    ///   .get("/users", |req, res| {
+    ///     res.status(StatusCode::Ok);
+    ///   });
+    /// ```
+    pub fn trailing_slash(&mut self, policy: TrailingSlash) -> &mut Self {
+        self.tree = None;
+        self.trailing_slash = Some(policy);
+        self
+    }
+
+    /// Sets whether the static parts of the routes match the request path regardless of the case
+    /// of the ASCII letters. Parameter values are captured as they are in the request.
+    ///
+    /// Routes are case-sensitive by default. The setting applies to the routes of the router and
+    /// of its subrouters, unless a subrouter sets its own. The path that the router is mounted at
+    /// is matched with the setting of the parent router.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, StatusCode };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// // Matches `/users/1`, `/Users/1` and `/USERS/1`
+    /// router.case_insensitive(true).get("/users/:id", |req, res| {
+    ///   res.status(StatusCode::Ok);
+    /// });
+    /// ```
+    pub fn case_insensitive(&mut self, enabled: bool) -> &mut Self {
+        self.tree = None;
+        self.case_insensitive = Some(enabled);
+        self
+    }
+
+    /// Compiles the routes of the router into a radix tree that is used to match the requests.
+    ///
+    /// The server compiles its routers once before it starts to accept connections, and a router
+    /// that is not compiled yet compiles itself on the first request. Adding a route or a router
+    /// after compiling discards the tree, so it is built again on the next request.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, StatusCode };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// router.get("/users/:id", |req, res| {
+    ///   res.status(StatusCode::Ok);
+    /// });
+    ///
+    /// router.compile();
+    /// ```
+    pub fn compile(&mut self) {
+        let mut tree = RadixTree::new();
+        let mut branches = Vec::new();
+
+        let policy = (TrailingSlash::default(), false);
+
+        self.insert_routes(
+            &mut tree,
+            &mut String::new(),
+            &mut Vec::new(),
+            &mut branches,
+            policy,
+        );
+        self.tree = Some(Arc::new(tree));
+    }
+
+    /// Inserts the routes of the router tree. `cases` is whether each segment of the pattern
+    /// ignores the case, which is decided by the router that the segment belongs to. `policy` is
+    /// the trailing slash policy and the case insensitivity inherited from the parent routers.
+    fn insert_routes(
+        &self,
+        tree: &mut RadixTree<Route>,
+        pattern: &mut String,
+        cases: &mut Vec<bool>,
+        branches: &mut Vec<Branch>,
+        policy: (TrailingSlash, bool),
     ) {
-        match iter.next() {
-            Some(PathType::Subdirectory(path)) => {
-                match router.subdirs.get_mut(&path) {
-                    Some(found_router) => {
-                        // Router Found
-                        Self::add_router(found_router, new_router, iter);
-                    }
-                    _ => {
-                        if iter.peek().is_some() {
-                            // No Router & Iteration Continues
-                            let mut inserted_router = Box::new(Router::new());
-                            Self::add_router(inserted_router.as_mut(), new_router, iter);
-                            router.subdirs.insert(path, inserted_router);
-                        } else {
-                            // No Router & Iteration Ends
-                            router.subdirs.insert(path, Box::new(new_router));
+        let trailing_slash = self.trailing_slash.unwrap_or(policy.0);
+        let ignore_case = self.case_insensitive.unwrap_or(policy.1);
+        let policy = (trailing_slash, ignore_case);
+
+        let mut endpoints = self.endpoints.iter().enumerate().collect::<Vec<_>>();
+
+        // Endpoints with guards are tried before the endpoints that accept every request
+        endpoints.sort_by_key(|(_, endpoint)| endpoint.get_guards().is_empty());
+
+        for (index, endpoint) in endpoints {
+            let route = Route {
+                branches: branches.clone(),
+                method: endpoint.get_method().clone(),
+                index,
+                trailing_slash,
+                guards: endpoint.get_guards().to_vec(),
+            };
+
+            tree.insert(pattern, cases, route);
+        }
+
+        let length = pattern.len();
+
+        for (subdir_index, (path, router)) in self.subdirs.iter().enumerate() {
+            pattern.push('/');
+            pattern.push_str(path);
+            cases.push(ignore_case);
+            branches.push(Branch::Subdirectory(subdir_index));
+
+            router.insert_routes(tree, pattern, cases, branches, policy);
+
+            branches.pop();
+            cases.pop();
+            pattern.truncate(length);
+        }
+
+        for (param_index, (param, router)) in self.param_dirs.iter().enumerate() {
+            pattern.push_str("/:");
+            pattern.push_str(param);
+            cases.push(ignore_case);
+            branches.push(Branch::Parameter(param_index));
+
+            router.insert_routes(tree, pattern, cases, branches, policy);
+
+            branches.pop();
+            cases.pop();
+            pattern.truncate(length);
+        }
+
+        for (mounted_index, router) in self.mounted.iter().enumerate() {
+            branches.push(Branch::Mounted(mounted_index));
+            router.insert_routes(tree, pattern, cases, branches, policy);
+            branches.pop();
+        }
+    }
+
+    /// Returns the router at the end of the path, inserting the routers that do not exist yet.
+    fn get_branch_or_insert(&mut self, path_types: Vec<PathType>) -> &mut Router {
+        let mut router = self;
+
+        for path_type in path_types {
+            router = match path_type {
+                PathType::Subdirectory(path) => {
+                    let index = match router.subdirs.iter().position(|(name, _)| *name == path) {
+                        Some(index) => index,
+                        None => {
+                            router.subdirs.push((path, Box::new(Router::new())));
+                            router.subdirs.len() - 1
                         }
-                    }
+                    };
+
+                    &mut router.subdirs[index].1
                 }
-            }
-            Some(PathType::Parameter(param)) => {
-                match &mut router.param_dir {
-                    Some(found_router) => {
-                        // Router Found
-                        Self::add_router(found_router.1.as_mut(), new_router, iter);
-                    }
-                    _ => {
-                        if iter.peek().is_some() {
-                            // No Router & Iteration Continues
-                            let mut inserted_router = Box::new(Router::new());
-                            Self::add_router(inserted_router.as_mut(), new_router, iter);
-                            router.param_dir = Some((param, Box::new(Router::new())));
-                        } else {
-                            // No Router & Iteration Ends
-                            router.param_dir = Some((param, Box::new(new_router)));
+                PathType::Parameter(param) => {
+                    let index = match router
+                        .param_dirs
+                        .iter()
+                        .position(|(name, _)| *name == param)
+                    {
+                        Some(index) => index,
+                        None => {
+                            router.param_dirs.push((param, Box::new(Router::new())));
+                            router.param_dirs.len() - 1
                         }
-                    }
+                    };
+
+                    &mut router.param_dirs[index].1
                 }
-            }
-            None => {
-                panic!("Route already exist. (Merging routers is not allowed for now.)");
-            }
+            };
         }
+
+        router
     }
 
-    fn add_endpoint<'a>(
-        router: &'a mut Router,
-        endpoint: Endpoint,
-        iter: &mut Peekable<std::vec::IntoIter<PathType>>,
-    ) {
-        match iter.next() {
-            Some(PathType::Subdirectory(path)) => {
-                if iter.peek().is_some() {
-                    // Iteration Will Continue
-                    match router.subdirs.get_mut(&path) {
-                        Some(found_router) => {
-                            // Router Found
-                            Self::add_endpoint(found_router, endpoint, iter);
-                        }
-                        _ => {
-                            // No Router
-                            let mut inserted_router = Box::new(Router::new());
-                            Self::add_endpoint(inserted_router.as_mut(), endpoint, iter);
-                            router.subdirs.insert(path, inserted_router);
-                        }
-                    }
-                } else {
-                    // Iteration Will End
-                    match router.subdirs.get_mut(&path) {
-                        Some(found_router) => {
-                            // Router Found
-                            found_router.endpoints.push(endpoint);
-                        }
-                        _ => {
-                            // No Router
-                            let mut inserted_router = Box::new(Router::new());
-                            inserted_router.endpoints.push(endpoint);
-                            router.subdirs.insert(path, inserted_router);
+    /// Returns the router of the middlewares at the end of the path, inserting the routers that do
+    /// not exist yet. Parameters lead to `any_param`, so that the middlewares run for the
+    /// parameters at the same position regardless of their names.
+    fn get_scope_or_insert(&mut self, path_types: Vec<PathType>) -> &mut Router {
+        let mut router = self;
+
+        for path_type in path_types {
+            router = match path_type {
+                PathType::Subdirectory(path) => {
+                    let index = match router.subdirs.iter().position(|(name, _)| *name == path) {
+                        Some(index) => index,
+                        None => {
+                            router.subdirs.push((path, Box::new(Router::new())));
+                            router.subdirs.len() - 1
                         }
-                    }
+                    };
+
+                    &mut router.subdirs[index].1
                 }
+                PathType::Parameter(_) => router
+                    .any_param
+                    .get_or_insert_with(|| Box::new(Router::new())),
+            };
+        }
+
+        router
+    }
+
+    /// Merges the other router into this one. Routes and fallbacks have to be checked for
+    /// conflicts before.
+    ///
+    /// A router with its own middlewares or policies is mounted next to this one instead of being
+    /// merged, so that they do not apply to the routes of this router. The middlewares of this
+    /// router that are at the paths of its routes still run for them, see [Router::find_through].
+    /// The middlewares at the parameters of both routers are merged.
+    fn merge(&mut self, other: Router) {
+        if !other.middlewares.is_empty()
+            || other.trailing_slash.is_some()
+            || other.case_insensitive.is_some()
+        {
+            self.mounted.push(other);
+            return;
+        }
+
+        self.endpoints.extend(other.endpoints);
+        self.mounted.extend(other.mounted);
+        self.fallback = self.fallback.or(other.fallback);
+
+        for (subdir, router) in other.subdirs {
+            match self.get_subdir_mut(&subdir) {
+                Some(found_router) => found_router.merge(*router),
+                None => self.subdirs.push((subdir, router)),
             }
-            Some(PathType::Parameter(param)) => {
-                if iter.peek().is_some() {
-                    // Iteration Will Continue
-                    match &mut router.param_dir {
-                        Some(found_router) => {
-                            // Router Found
-                            Self::add_endpoint(found_router.1.as_mut(), endpoint, iter);
-                        }
-                        _ => {
-                            // No Router
-                            let mut inserted_router = Box::new(Router::new());
-                            Self::add_endpoint(inserted_router.as_mut(), endpoint, iter);
-                            router.param_dir = Some((param, inserted_router));
-                        }
-                    }
-                } else {
-                    // Iteration Will End
-                    match &mut router.param_dir {
-                        Some(found_router) => {
-                            // Router Found
-                            found_router.1.endpoints.push(endpoint);
-                        }
-                        _ => {
-                            // No Router
-                            let mut inserted_router = Box::new(Router::new());
-                            inserted_router.endpoints.push(endpoint);
-                            router.param_dir = Some((param, inserted_router));
-                        }
-                    }
-                }
+        }
+
+        for (param, router) in other.param_dirs {
+            match self.get_param_dir_mut(&param) {
+                Some(found_router) => found_router.merge(*router),
+                None => self.param_dirs.push((param, router)),
             }
-            None => {
-                panic!("Error: Route already exist.")
+        }
+
+        if let Some(router) = other.any_param {
+            self.any_param
+                .get_or_insert_with(|| Box::new(Router::new()))
+                .merge_scope(*router);
+        }
+    }
+
+    /// Merges a router that only has middlewares into this one. Such routers are created by
+    /// [Router::get_scope_or_insert] under `any_param`.
+    fn merge_scope(&mut self, other: Router) {
+        self.middlewares.extend(other.middlewares);
+
+        for (subdir, router) in other.subdirs {
+            match self.get_subdir_mut(&subdir) {
+                Some(found_router) => found_router.merge_scope(*router),
+                None => self.subdirs.push((subdir, router)),
             }
         }
+
+        if let Some(router) = other.any_param {
+            self.any_param
+                .get_or_insert_with(|| Box::new(Router::new()))
+                .merge_scope(*router);
+        }
     }
 
-    fn route_handler<'a>(
-        &'a mut self,
-        path_array: &Vec<String>,
-        method: &HttpMethod,
-    ) -> RouterResult<'a> {
-        let params: HashMap<String, String> = HashMap::new();
-        let iter: Iter<'_, String> = path_array.iter();
+    /// Collects the path, the method and whether the endpoint has guards for every endpoint in the
+    /// router tree. `path` is the path of this router.
+    fn collect_routes(
+        &self,
+        path: &mut Vec<PathType>,
+        routes: &mut Vec<(Vec<PathType>, HttpMethod, bool)>,
+    ) {
+        for endpoint in &self.endpoints {
+            let guarded = !endpoint.get_guards().is_empty();
+            routes.push((path.clone(), endpoint.get_method().clone(), guarded));
+        }
+
+        for (subdir, router) in &self.subdirs {
+            path.push(PathType::Subdirectory(subdir.clone()));
+            router.collect_routes(path, routes);
+            path.pop();
+        }
+
+        for (param, router) in &self.param_dirs {
+            path.push(PathType::Parameter(param.clone()));
+            router.collect_routes(path, routes);
+            path.pop();
+        }
 
-        Self::handle_routes(self, method, params, iter)
+        for router in &self.mounted {
+            router.collect_routes(path, routes);
+        }
     }
 
-    fn handle_routes<'a, 'b>(
-        router: &'a mut Router,
+    /// Collects the path of every fallback in the router tree. `path` is the path of this router.
+    fn collect_fallbacks(&self, path: &mut Vec<PathType>, fallbacks: &mut Vec<Vec<PathType>>) {
+        if self.fallback.is_some() {
+            fallbacks.push(path.clone());
+        }
+
+        for (subdir, router) in &self.subdirs {
+            path.push(PathType::Subdirectory(subdir.clone()));
+            router.collect_fallbacks(path, fallbacks);
+            path.pop();
+        }
+
+        for (param, router) in &self.param_dirs {
+            path.push(PathType::Parameter(param.clone()));
+            router.collect_fallbacks(path, fallbacks);
+            path.pop();
+        }
+
+        for router in &self.mounted {
+            router.collect_fallbacks(path, fallbacks);
+        }
+    }
+
+    /// Checks if a new route conflicts with the routes of the router. Endpoints with guards can
+    /// share their method and path with other endpoints, so they never conflict.
+    fn check_route(
+        &self,
+        path_types: &[PathType],
         method: &HttpMethod,
-        mut params: HashMap<String, String>,
-        mut iter: Iter<'_, String>,
-    ) -> RouterResult<'a> {
-        if let Some(route) = iter.next() {
-            let route = route
-                .split('?')
-                .collect::<Vec<&str>>()
-                .first()
-                .unwrap()
-                .to_string();
-
-            if route.is_empty() {
-                return Self::handle_routes(router, method, params, iter);
+        guarded: bool,
+    ) -> Result<(), RouterError> {
+        let path = PathType::to_path(path_types);
+
+        let param_count = path_types
+            .iter()
+            .filter(|path_type| matches!(path_type, PathType::Parameter(_)))
+            .count();
+
+        if param_count > radix::MAX_PARAMS {
+            return Err(RouterError::TooManyParameters(path));
+        }
+
+        for (index, path_type) in path_types.iter().enumerate() {
+            let PathType::Parameter(param) = path_type else {
+                continue;
+            };
+
+            if path_types[..index].contains(path_type) {
+                return Err(RouterError::DuplicateParameter {
+                    path,
+                    param: param.clone(),
+                });
+            }
+        }
+
+        if guarded {
+            return Ok(());
+        }
+
+        let same_route = |router: &Router| {
+            router
+                .endpoints
+                .iter()
+                .any(|endpoint| endpoint.get_method() == method && endpoint.get_guards().is_empty())
+        };
+
+        match self.find_same(path_types, &same_route) {
+            Some(existing) if existing == path => Err(RouterError::DuplicateRoute {
+                method: method.clone(),
+                path,
+            }),
+            Some(existing) => Err(RouterError::ConflictingParameters {
+                method: method.clone(),
+                path,
+                existing,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Finds a router at the path that satisfies `found`, regardless of the parameter names, and
+    /// returns its path.
+    fn find_same(
+        &self,
+        path_types: &[PathType],
+        found: &impl Fn(&Router) -> bool,
+    ) -> Option<String> {
+        let join = |segment: String, rest: String| match rest.as_str() {
+            "/" => format!("/{segment}"),
+            _ => format!("/{segment}{rest}"),
+        };
+
+        let same = match path_types.split_first() {
+            None => found(self).then(|| String::from("/")),
+            Some((PathType::Subdirectory(subdir), rest)) => self
+                .subdirs
+                .iter()
+                .find(|(name, _)| name == subdir)
+                .and_then(|(_, router)| router.find_same(rest, found))
+                .map(|rest| join(subdir.clone(), rest)),
+            Some((PathType::Parameter(_), rest)) => {
+                self.param_dirs.iter().find_map(|(param, router)| {
+                    let rest = router.find_same(rest, found)?;
+                    Some(join(format!(":{param}"), rest))
+                })
             }
-            // Iteration Continues
-            match router.subdirs.get_mut(route.as_str()) {
-                Some(founded_router) => {
-                    // Router Found
-                    Self::handle_routes(founded_router.as_mut(), method, params, iter)
+        };
+
+        same.or_else(|| {
+            self.mounted
+                .iter()
+                .find_map(|router| router.find_same(path_types, found))
+        })
+    }
+
+    fn get_subdir_mut(&mut self, subdir: &str) -> Option<&mut Router> {
+        self.subdirs
+            .iter_mut()
+            .find(|(name, _)| name == subdir)
+            .map(|(_, router)| router.as_mut())
+    }
+
+    fn get_param_dir_mut(&mut self, param: &str) -> Option<&mut Router> {
+        self.param_dirs
+            .iter_mut()
+            .find(|(name, _)| name == param)
+            .map(|(_, router)| router.as_mut())
+    }
+
+    /// Runs the middlewares of the router around the next router on the branches, or around the
+    /// target when the end of the branches is reached.
+    fn dispatch(
+        &mut self,
+        branches: &[Branch],
+        target: Target,
+        request: &Request,
+        response: &mut Response,
+    ) -> HandlerResult {
+        let through = match branches.split_first() {
+            Some((branch, rest)) => self.find_through(branch, rest),
+            None => Vec::new(),
+        };
+
+        let Router {
+            endpoints,
+            middlewares,
+            subdirs,
+            param_dirs,
+            any_param,
+            mounted,
+            fallback,
+            ..
+        } = self;
+
+        run_middlewares(middlewares, request, response, &mut |request, response| {
+            let Some((branch, rest)) = branches.split_first() else {
+                let endpoint = match target {
+                    Target::Endpoint(index) => endpoints.get_mut(index),
+                    Target::Fallback | Target::Rejected(_) => None,
+                };
+
+                if let Some(endpoint) = endpoint {
+                    return endpoint.handle(request, response);
                 }
-                _ => {
-                    match router.param_dir.as_mut() {
-                        Some((param_name, founded_router)) => {
-                            // Parameter Found
-                            params.insert(param_name.clone(), route);
-                            Self::handle_routes(founded_router, method, params, iter)
-                        }
-                        _ => None,
+
+                match (*fallback, target) {
+                    (_, Target::Rejected(status_code)) => {
+                        response.status(status_code);
+                    }
+                    (Some(controller), Target::Fallback) => controller(request, response),
+                    _ => {
+                        response.status(StatusCode::NotFound);
                     }
                 }
+
+                return HandlerResult::Next;
+            };
+
+            let child = match branch {
+                // Nothing runs through the subdirectories, as they are on the way themselves
+                Branch::Subdirectory(subdir_index) => {
+                    return match subdirs.get_mut(*subdir_index) {
+                        Some((_, router)) => router.dispatch(rest, target, request, response),
+                        None => Self::not_found(response),
+                    };
+                }
+                Branch::Parameter(param_index) => param_dirs
+                    .get_mut(*param_index)
+                    .map(|(_, router)| router.as_mut()),
+                Branch::Mounted(mounted_index) => mounted.get_mut(*mounted_index),
+                Branch::AnyParameter => None,
+            };
+
+            match child {
+                Some(router) => Self::run_through(
+                    subdirs,
+                    any_param,
+                    &through,
+                    request,
+                    response,
+                    &mut |request, response| router.dispatch(rest, target, request, response),
+                ),
+                None => Self::not_found(response),
             }
-        } else {
-            // Iteration Ends
-            match router
-                .endpoints
-                .iter_mut()
-                .find(|endpoint| endpoint.is_method(method))
-            {
-                Some(endpoint) => Some((endpoint, params)),
-                None => None,
+        })
+    }
+
+    fn not_found(response: &mut Response) -> HandlerResult {
+        response.status(StatusCode::NotFound);
+        HandlerResult::Next
+    }
+
+    /// Finds the routers of the middlewares that are at the same path as the next router on the
+    /// branches but not on the way to it, so that they run for its routes as well. These are the
+    /// middlewares at the parameters, which run for every parameter at the same position, and the
+    /// middlewares of this router at the paths of a mounted router.
+    fn find_through(&self, branch: &Branch, rest: &[Branch]) -> Vec<Branch> {
+        let mut through = Vec::new();
+        let mut shape = Vec::new();
+
+        match branch {
+            Branch::Subdirectory(_) | Branch::AnyParameter => {}
+            Branch::Parameter(index) => {
+                let (Some(scope), Some((_, router))) = (&self.any_param, self.param_dirs.get(*index))
+                else {
+                    return through;
+                };
+
+                router.collect_shape(rest, &mut shape);
+                through.push(Branch::AnyParameter);
+                Self::find_scope(&scope.subdirs, &scope.any_param, &shape, &mut through);
+            }
+            Branch::Mounted(index) => {
+                let Some(router) = self.mounted.get(*index) else {
+                    return through;
+                };
+
+                router.collect_shape(rest, &mut shape);
+                Self::find_scope(&self.subdirs, &self.any_param, &shape, &mut through);
+            }
+        }
+
+        through
+    }
+
+    /// Collects the segments of the path that the branches lead to, with `None` for the
+    /// parameters.
+    fn collect_shape<'a>(&'a self, branches: &[Branch], shape: &mut Vec<Option<&'a str>>) {
+        let Some((branch, rest)) = branches.split_first() else {
+            return;
+        };
+
+        let router = match branch {
+            Branch::Subdirectory(index) => self.subdirs.get(*index).map(|(subdir, router)| {
+                shape.push(Some(subdir.as_str()));
+                router.as_ref()
+            }),
+            Branch::Parameter(index) => self.param_dirs.get(*index).map(|(_, router)| {
+                shape.push(None);
+                router.as_ref()
+            }),
+            Branch::Mounted(index) => self.mounted.get(*index),
+            Branch::AnyParameter => None,
+        };
+
+        if let Some(router) = router {
+            router.collect_shape(rest, shape);
+        }
+    }
+
+    /// Follows the segments of the path through `subdirs` and `any_param` as far as there are
+    /// routers for them.
+    fn find_scope(
+        subdirs: &[(String, Box<Router>)],
+        any_param: &Option<Box<Router>>,
+        shape: &[Option<&str>],
+        through: &mut Vec<Branch>,
+    ) {
+        let Some((segment, rest)) = shape.split_first() else {
+            return;
+        };
+
+        let found = match segment {
+            Some(segment) => subdirs
+                .iter()
+                .position(|(subdir, _)| subdir == segment)
+                .map(|index| (Branch::Subdirectory(index), &subdirs[index].1)),
+            None => any_param
+                .as_ref()
+                .map(|router| (Branch::AnyParameter, router)),
+        };
+
+        if let Some((branch, router)) = found {
+            through.push(branch);
+            Self::find_scope(&router.subdirs, &router.any_param, rest, through);
+        }
+    }
+
+    /// Runs the middlewares of the routers on the `through` branches around `last`, without
+    /// running their endpoints or fallbacks.
+    fn run_through(
+        subdirs: &mut [(String, Box<Router>)],
+        any_param: &mut Option<Box<Router>>,
+        through: &[Branch],
+        request: &Request,
+        response: &mut Response,
+        last: &mut Continuation<'_>,
+    ) -> HandlerResult {
+        let router = match through.first() {
+            Some(Branch::Subdirectory(index)) => subdirs.get_mut(*index).map(|(_, router)| router),
+            Some(Branch::AnyParameter) => any_param.as_mut(),
+            Some(Branch::Parameter(_) | Branch::Mounted(_)) | None => None,
+        };
+
+        let Some(router) = router else {
+            return last(request, response);
+        };
+
+        let Router {
+            middlewares,
+            subdirs,
+            any_param,
+            ..
+        } = router.as_mut();
+
+        run_middlewares(middlewares, request, response, &mut |request, response| {
+            Self::run_through(subdirs, any_param, &through[1..], request, response, last)
+        })
+    }
+
+    /// Finds the deepest router on the path that has a fallback. `found` keeps the number of
+    /// segments left after that router, the branches to it and the parameters captured on the
+    /// way. `ignore_case` is the case insensitivity inherited from the parent routers.
+    fn find_fallback(
+        &self,
+        segments: &[&str],
+        branches: &mut Vec<Branch>,
+        params: &mut HashMap<String, String>,
+        found: &mut Option<(usize, Vec<Branch>, HashMap<String, String>)>,
+        ignore_case: bool,
+    ) {
+        let ignore_case = self.case_insensitive.unwrap_or(ignore_case);
+
+        let deeper = found
+            .as_ref()
+            .is_none_or(|(left, _, _)| segments.len() < *left);
+
+        if self.fallback.is_some() && deeper {
+            *found = Some((segments.len(), branches.clone(), params.clone()));
+        }
+
+        for (mounted_index, router) in self.mounted.iter().enumerate() {
+            branches.push(Branch::Mounted(mounted_index));
+            router.find_fallback(segments, branches, params, found, ignore_case);
+            branches.pop();
+        }
+
+        let Some((segment, rest)) = segments.split_first() else {
+            return;
+        };
+
+        let subdirs = self
+            .subdirs
+            .iter()
+            .enumerate()
+            .filter(|(_, (path, _))| match ignore_case {
+                true => path.eq_ignore_ascii_case(segment),
+                false => path == segment,
+            });
+
+        for (subdir_index, (_, router)) in subdirs {
+            branches.push(Branch::Subdirectory(subdir_index));
+            router.find_fallback(rest, branches, params, found, ignore_case);
+            branches.pop();
+        }
+
+        for (param_index, (param, router)) in self.param_dirs.iter().enumerate() {
+            branches.push(Branch::Parameter(param_index));
+            let previous = params.insert(param.clone(), Self::decode_param(segment));
+
+            router.find_fallback(rest, branches, params, found, ignore_case);
+
+            branches.pop();
+            match previous {
+                Some(value) => params.insert(param.clone(), value),
+                None => params.remove(param),
+            };
+        }
+    }
+
+    /// Percent-decodes the value of a parameter. Values that cannot be decoded are kept as they
+    /// are, since the server rejects such requests before they reach the router.
+    fn decode_param(value: &str) -> String {
+        percent_decode(value, false).unwrap_or_else(|_| value.to_string())
+    }
+
+    /// Ranks a route that matches the path by how well it suits the request. It is `None` if the
+    /// guards of the route reject the request. Routes with guards rank above the ones without,
+    /// and then by the quality that the request gives to the media type of their
+    /// [Endpoint::accepts] guards.
+    fn rank(route: &Route, request: &Request, matches_path: bool) -> Option<(bool, f32)> {
+        if !matches_path || !route.guards.iter().all(|guard| guard.check(request)) {
+            return None;
+        }
+
+        let quality = route
+            .guards
+            .iter()
+            .filter_map(|guard| guard.get_quality(request))
+            .fold(1.0, f32::min);
+
+        Some((!route.guards.is_empty(), quality))
+    }
+
+    /// Adds the decoded parameters of a route to the request. The request is only copied when
+    /// the route has parameters.
+    fn with_params<'r>(request: &'r Request, params: Params<'_, '_>) -> Cow<'r, Request> {
+        let mut params = params
+            .iter()
+            .map(|(key, value)| (key.to_string(), Self::decode_param(value)))
+            .peekable();
+
+        match params.peek() {
+            Some(_) => Cow::Owned(request.with_params(params)),
+            None => Cow::Borrowed(request),
+        }
+    }
+
+    /// Replaces the repeated slashes of the path with a single one, so that empty segments, as
+    /// in `/users//1`, are ignored.
+    fn collapse_slashes(path: &str) -> Cow<'_, str> {
+        if !path.contains("//") {
+            return Cow::Borrowed(path);
+        }
+
+        let mut collapsed = String::with_capacity(path.len());
+
+        for char in path.chars() {
+            if !(char == '/' && collapsed.ends_with('/')) {
+                collapsed.push(char);
             }
         }
+
+        Cow::Owned(collapsed)
+    }
+
+    /// Returns the path without the trailing slashes, `/` for the root.
+    fn trim_trailing_slash(path: &str) -> &str {
+        let path = path.trim_end_matches('/');
+
+        if path.is_empty() { "/" } else { path }
     }
 
-    fn get_path_types(path: &str) -> Vec<PathType> {
+    /// Sets a redirect from the request target to its canonical path, keeping the query.
+    fn redirect(request: &Request, path: &str, status: StatusCode, response: &mut Response) {
+        let location = match request.get_raw_query() {
+            Some(query) => format!("{path}?{query}"),
+            None => path.to_string(),
+        };
+
+        response.status(status).set_header("Location", &location);
+    }
+
+    fn try_get_path_types(path: &str) -> Result<Vec<PathType>, RouterError> {
         path.split('/')
-            .into_iter()
             .filter(|path| !path.is_empty())
-            .map(|path| match PathType::try_from(path) {
-                Ok(path_type) => path_type,
-                Err(err) => panic!("Error while adding router: {}", err),
-            })
-            .collect::<Vec<PathType>>()
+            .map(|path| PathType::try_from(path).map_err(RouterError::from))
+            .collect::<Result<Vec<PathType>, RouterError>>()
     }
 }
 
 impl RouteHandler for Router {
+    fn compile(&mut self) {
+        Router::compile(self);
+    }
+
     fn handle(&mut self, request: &Request, response: &mut Response) -> HandlerResult {
-        while let Some(middleware) = self.middlewares.iter_mut().next() {
-            match middleware.middleware(request, response) {
-                HandlerResult::End => {
-                    return HandlerResult::End;
-                }
-                HandlerResult::Next => (),
-            }
+        if self.tree.is_none() {
+            self.compile();
         }
 
-        match self.route_handler(request.get_path_array(), request.get_method()) {
-            Some((endpoint, params)) => {
-                while let Some(middleware) = endpoint.get_middlewares().iter_mut().next() {
-                    match middleware.middleware(request, response) {
-                        HandlerResult::End => {
-                            return HandlerResult::End;
-                        }
-                        HandlerResult::Next => (),
-                    }
-                }
+        let Some(tree) = self.tree.clone() else {
+            return HandlerResult::Next;
+        };
 
-                let mut request = request.clone();
-                request.add_param(params);
+        let method = request.get_method();
+        let decoded = decode_unreserved(request.get_path());
+        let path = Self::collapse_slashes(&decoded);
+        let trimmed = Self::trim_trailing_slash(&path);
+        let has_trailing_slash = trimmed.len() < path.len();
 
-                endpoint.get_controller()(&request, response);
-                return HandlerResult::Next;
+        let matches_path = |route: &Route| {
+            route.method == *method
+                && !(has_trailing_slash && route.trailing_slash == TrailingSlash::Strict)
+        };
+        let found = tree.find(trimmed, |route| {
+            Self::rank(route, request, matches_path(route))
+        });
+
+        if found.is_none() {
+            let rejected = tree.find(trimmed, |route| matches_path(route).then_some(()));
+
+            if let Some((route, params)) = rejected {
+                let status_code = route
+                    .guards
+                    .iter()
+                    .find(|guard| !guard.check(request))
+                    .map_or(StatusCode::NotFound, Guard::get_status);
+
+                return self.dispatch(
+                    &route.branches,
+                    Target::Rejected(status_code),
+                    &Self::with_params(request, params),
+                    response,
+                );
             }
-            None => {
-                response.status(StatusCode::NotFound);
-                return HandlerResult::Next;
+        }
+
+        let Some((route, params)) = found else {
+            let segments = trimmed
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .collect::<Vec<&str>>();
+
+            let mut branches = Vec::new();
+            let mut params = HashMap::new();
+            let mut found = None;
+
+            self.find_fallback(&segments, &mut branches, &mut params, &mut found, false);
+
+            let (_, branches, params) = found.unwrap_or_default();
+            let request = match params.is_empty() {
+                true => Cow::Borrowed(request),
+                false => Cow::Owned(request.with_params(params)),
+            };
+
+            return self.dispatch(&branches, Target::Fallback, &request, response);
+        };
+
+        if has_trailing_slash {
+            match route.trailing_slash {
+                TrailingSlash::MovedPermanently => {
+                    Self::redirect(request, trimmed, StatusCode::MovedPermanently, response);
+                    return HandlerResult::Next;
+                }
+                TrailingSlash::PermanentRedirect => {
+                    Self::redirect(request, trimmed, StatusCode::PermanentRedirect, response);
+                    return HandlerResult::Next;
+                }
+                TrailingSlash::Lenient | TrailingSlash::Strict => {}
             }
         }
+
+        self.dispatch(
+            &route.branches,
+            Target::Endpoint(route.index),
+            &Self::with_params(request, params),
+            response,
+        )
     }
 }
 
@@ -358,40 +1325,121 @@ impl Clone for Router {
     fn clone(&self) -> Self {
         let endpoints: Vec<Endpoint> = self.endpoints.clone();
         let middlewares: Vec<Box<dyn Middleware>> = self.middlewares.clone();
-        let subdirs: HashMap<String, Box<Router>> = self.subdirs.clone();
-        let param_dir: Option<(String, Box<Router>)> = match &self.param_dir {
-            Some((key, router)) => Some((key.clone(), router.clone())),
-            None => None,
-        };
+        let subdirs: Vec<(String, Box<Router>)> = self.subdirs.clone();
+        let param_dirs: Vec<(String, Box<Router>)> = self.param_dirs.clone();
+        let any_param: Option<Box<Router>> = self.any_param.clone();
+        let mounted: Vec<Router> = self.mounted.clone();
+        let fallback: Option<Controller> = self.fallback;
+        let trailing_slash: Option<TrailingSlash> = self.trailing_slash;
+        let case_insensitive: Option<bool> = self.case_insensitive;
+        let last_endpoint: Option<(Vec<PathType>, usize)> = self.last_endpoint.clone();
+        let tree: Option<Arc<RadixTree<Route>>> = self.tree.clone();
 
         Self {
             endpoints,
             middlewares,
             subdirs,
-            param_dir,
+            param_dirs,
+            any_param,
+            mounted,
+            fallback,
+            trailing_slash,
+            case_insensitive,
+            last_endpoint,
+            tree,
         }
     }
 }
 
-#[derive(Eq, Hash, PartialEq, Debug)]
+impl Default for Router {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Locates an endpoint in the router tree. Values of the compiled radix tree.
+#[derive(Debug)]
+struct Route {
+    branches: Vec<Branch>,
+    method: HttpMethod,
+    index: usize,
+    trailing_slash: TrailingSlash,
+    guards: Vec<Guard>,
+}
+
+/// How a router treats a trailing slash at the end of the request path.
+///
+/// The canonical form of a path has no trailing slash, except the root path `/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailingSlash {
+    /// Matches the path with or without a trailing slash
+    #[default]
+    Lenient,
+    /// Matches only the canonical form of the path
+    Strict,
+    /// Redirects to the canonical form with `301 Moved Permanently`
+    MovedPermanently,
+    /// Redirects to the canonical form with `308 Permanent Redirect`, which keeps the method
+    /// and the body of the request
+    PermanentRedirect,
+}
+
+/// What is run at the end of the branches while dispatching a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Endpoint(usize),
+    Fallback,
+    /// An endpoint matches the method and the path but its guards reject the request.
+    Rejected(StatusCode),
+}
+
+/// A step taken from a router to one of its child routers while matching a route.
+#[derive(Debug, Clone)]
+enum Branch {
+    Subdirectory(usize),
+    Parameter(usize),
+    /// A router that is mounted at the same path, see [Router::merge].
+    Mounted(usize),
+    /// The router of the middlewares at a parameter, which is only on the way to the middlewares
+    /// and never to a route, see [Router::find_through].
+    AnyParameter,
+}
+
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
 enum PathType {
     Subdirectory(String),
     Parameter(String),
 }
 
+impl PathType {
+    fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    }
+
+    fn to_path(path_types: &[PathType]) -> String {
+        if path_types.is_empty() {
+            return String::from("/");
+        }
+
+        path_types
+            .iter()
+            .fold(String::new(), |path, path_type| match path_type {
+                PathType::Subdirectory(subdir) => format!("{path}/{subdir}"),
+                PathType::Parameter(param) => format!("{path}/:{param}"),
+            })
+    }
+}
+
 impl TryFrom<&str> for PathType {
     type Error = ParsePathTypeError;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        if value.starts_with(':') {
-            return Ok(Self::Parameter(value.chars().skip(1).collect::<String>()));
-        } else {
-            let regex = Regex::new(r"^[a-zA-Z0-9_\-.]+$").unwrap();
-
-            if regex.is_match(value) {
-                return Ok(Self::Subdirectory(value.to_string()));
-            } else {
-                return Err(ParsePathTypeError(value.to_string()));
-            }
+        match value.strip_prefix(':') {
+            Some(param) if Self::is_valid_name(param) => Ok(Self::Parameter(param.to_string())),
+            None if Self::is_valid_name(value) => Ok(Self::Subdirectory(value.to_string())),
+            _ => Err(ParsePathTypeError(value.to_string())),
         }
     }
 }
@@ -405,3 +1453,77 @@ impl Display for ParsePathTypeError {
         write!(f, "Invalid path value for router: {}", self.0)
     }
 }
+
+/// Error returned when a route can not be added to a [Router].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouterError {
+    /// A segment of the path is not valid. Segments can only contain alphanumeric characters,
+    /// `_`, `-` and `.`, and parameters need a name after the `:`.
+    InvalidSegment(String),
+    /// There is already an endpoint with the same method for the path.
+    DuplicateRoute {
+        /// Method of the route
+        method: HttpMethod,
+        /// Path of the route
+        path: String,
+    },
+    /// There is already an endpoint with the same method for the same path but with different
+    /// parameter names, so the new endpoint would never be reached.
+    ConflictingParameters {
+        /// Method of the route
+        method: HttpMethod,
+        /// Path of the new route
+        path: String,
+        /// Path of the existing route
+        existing: String,
+    },
+    /// The route has more parameters than the router supports.
+    TooManyParameters(String),
+    /// The route has two parameters with the same name, so the value of the first one would be
+    /// lost.
+    DuplicateParameter {
+        /// Path of the route
+        path: String,
+        /// Name of the parameter
+        param: String,
+    },
+    /// There is already a route with the name.
+    DuplicateName(String),
+    /// There is already a fallback at the path.
+    DuplicateFallback(String),
+}
+
+impl Display for RouterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSegment(segment) => write!(f, "Invalid path value for router: {segment}"),
+            Self::DuplicateRoute { method, path } => {
+                write!(f, "Route already exist: {method} {path}")
+            }
+            Self::ConflictingParameters {
+                method,
+                path,
+                existing,
+            } => write!(
+                f,
+                "Route {method} {path} conflicts with {method} {existing} (parameter names differ)"
+            ),
+            Self::TooManyParameters(path) => write!(
+                f,
+                "Route {path} has more than {} parameters",
+                radix::MAX_PARAMS
+            ),
+            Self::DuplicateParameter { path, param } => {
+                write!(f, "Route {path} has more than one parameter named {param}")
+            }
+            Self::DuplicateName(name) => write!(f, "Route name already exist: {name}"),
+            Self::DuplicateFallback(path) => write!(f, "Fallback already exist: {path}"),
+        }
+    }
+}
+
+impl From<ParsePathTypeError> for RouterError {
+    fn from(err: ParsePathTypeError) -> Self {
+        Self::InvalidSegment(err.0)
+    }
+}
@@ -0,0 +1,212 @@
+use crate::Middleware;
+
+use super::{Controller, Endpoint, HttpMethod, PathType, Router};
+
+/// Adds endpoints for several methods to a path without repeating it. It is created by
+/// [Router::route].
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::{ Router, Request, Response, StatusCode, Middleware };
+///
+/// #[derive(Clone)]
+/// struct Auth;
+///
+/// impl Middleware for Auth {}
+///
+/// fn show(req: &Request, res: &mut Response) {
+///   res.status(StatusCode::Ok);
+/// }
+///
+/// fn update(req: &Request, res: &mut Response) {
+///   res.status(StatusCode::Ok);
+/// }
+///
+/// fn destroy(req: &Request, res: &mut Response) {
+///   res.status(StatusCode::NoContent);
+/// }
+///
+/// let mut router = Router::new();
+///
+/// router
+///   .route("/users/:id")
+///   .get(show)
+///   .put(update)
+///   .delete(destroy)
+///   .middleware(Auth);
+/// ```
+#[derive(Debug)]
+pub struct RouteBuilder<'a> {
+    router: &'a mut Router,
+    path_types: Vec<PathType>,
+    endpoints: Vec<usize>,
+    middlewares: Vec<Box<dyn Middleware>>,
+}
+
+impl Router {
+    /// Returns a [RouteBuilder] that adds endpoints to the path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path has an invalid segment.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, StatusCode };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// router
+    ///   .route("/users")
+    ///   .get(|req, res| {
+    ///     res.status(StatusCode::Ok);
+    ///   })
+    ///   .post(|req, res| {
+    ///     res.status(StatusCode::Created);
+    ///   });
+    /// ```
+    pub fn route(&mut self, path: &str) -> RouteBuilder<'_> {
+        let path_types = match Self::try_get_path_types(path.trim()) {
+            Ok(path_types) => path_types,
+            Err(err) => panic!("Error while adding route: {err}"),
+        };
+
+        RouteBuilder {
+            router: self,
+            path_types,
+            endpoints: Vec::new(),
+            middlewares: Vec::new(),
+        }
+    }
+}
+
+impl RouteBuilder<'_> {
+    /// Adds a GET endpoint to the path
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is already an endpoint with the method for the path.
+    pub fn get(&mut self, controller: Controller) -> &mut Self {
+        self.methods(&[HttpMethod::GET], controller)
+    }
+
+    /// Adds a POST endpoint to the path
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is already an endpoint with the method for the path.
+    pub fn post(&mut self, controller: Controller) -> &mut Self {
+        self.methods(&[HttpMethod::POST], controller)
+    }
+
+    /// Adds a PUT endpoint to the path
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is already an endpoint with the method for the path.
+    pub fn put(&mut self, controller: Controller) -> &mut Self {
+        self.methods(&[HttpMethod::PUT], controller)
+    }
+
+    /// Adds a PATCH endpoint to the path
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is already an endpoint with the method for the path.
+    pub fn patch(&mut self, controller: Controller) -> &mut Self {
+        self.methods(&[HttpMethod::PATCH], controller)
+    }
+
+    /// Adds a DELETE endpoint to the path
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is already an endpoint with the method for the path.
+    pub fn delete(&mut self, controller: Controller) -> &mut Self {
+        self.methods(&[HttpMethod::DELETE], controller)
+    }
+
+    /// Adds an endpoint for every method in [HttpMethod::ALL] to the path
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is already an endpoint with one of the methods for the path.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, StatusCode };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// router.route("/teapot").any(|req, res| {
+    ///   res.status(StatusCode::IAmATeapot);
+    /// });
+    /// ```
+    pub fn any(&mut self, controller: Controller) -> &mut Self {
+        self.methods(HttpMethod::ALL, controller)
+    }
+
+    /// Adds an endpoint for every method in [HttpMethod::ALL] to the path. Same as
+    /// [RouteBuilder::any].
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is already an endpoint with one of the methods for the path.
+    pub fn all(&mut self, controller: Controller) -> &mut Self {
+        self.any(controller)
+    }
+
+    /// Adds an endpoint for each of the methods to the path, all with the same controller
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is already an endpoint with one of the methods for the path.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, StatusCode, HttpMethod };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// router
+    ///   .route("/users/:id")
+    ///   .methods(&[HttpMethod::PUT, HttpMethod::PATCH], |req, res| {
+    ///     res.status(StatusCode::Ok);
+    ///   });
+    /// ```
+    pub fn methods(&mut self, methods: &[HttpMethod], controller: Controller) -> &mut Self {
+        for method in methods {
+            let endpoint =
+                Endpoint::new_with_middleware(method.clone(), controller, self.middlewares.clone());
+
+            if let Err(err) = self.router.add_endpoint(self.path_types.clone(), endpoint) {
+                panic!("Error while adding endpoint: {err}");
+            }
+
+            if let Some((_, index)) = self.router.last_endpoint {
+                self.endpoints.push(index);
+            }
+        }
+
+        self
+    }
+
+    /// Adds a middleware to every endpoint of the builder, including the endpoints that are added
+    /// after it. Middlewares of an endpoint run in the order they are added.
+    pub fn middleware(&mut self, middleware: impl Middleware + 'static) -> &mut Self {
+        let middleware: Box<dyn Middleware> = Box::new(middleware);
+
+        let router = self.router.get_branch_or_insert(self.path_types.clone());
+
+        for index in &self.endpoints {
+            router.endpoints[*index].add_middleware(middleware.clone());
+        }
+
+        self.middlewares.push(middleware);
+        self
+    }
+}
@@ -1,13 +1,24 @@
-use crate::{HttpMethod, Middleware};
+use crate::{
+    middleware::run_middlewares, server::route_handler::HandlerResult, HttpMethod, Middleware,
+    Request, Response, StatusCode,
+};
 
-use super::Controller;
+use super::{
+    guard::Guard,
+    openapi::RouteDoc,
+    resource::{Action, BoundAction},
+    Controller,
+};
 
 #[doc = include_str!("../../docs/core/endpoint.md")]
 #[derive(Debug)]
 pub struct Endpoint {
     method: HttpMethod,
-    controller: Controller,
+    handler: Handler,
     middlewares: Vec<Box<dyn Middleware>>,
+    name: Option<String>,
+    doc: Option<RouteDoc>,
+    guards: Vec<Guard>,
 }
 
 impl Endpoint {
@@ -27,8 +38,11 @@ impl Endpoint {
     pub fn new(method: HttpMethod, controller: Controller) -> Self {
         Self {
             method,
-            controller,
+            handler: Handler::Controller(controller),
             middlewares: Vec::new(),
+            name: None,
+            doc: None,
+            guards: Vec::new(),
         }
     }
 
@@ -65,21 +79,195 @@ impl Endpoint {
     ) -> Self {
         Self {
             method,
-            controller,
+            handler: Handler::Controller(controller),
             middlewares,
+            name: None,
+            doc: None,
+            guards: Vec::new(),
         }
     }
 
-    pub(crate) fn is_method(&self, method: &HttpMethod) -> bool {
-        self.method == *method
+    /// Creates an endpoint that runs an action of a resource, which is `None` if the resource does
+    /// not implement it. `actions` are the actions that the resource implements.
+    pub(crate) fn from_resource(
+        method: HttpMethod,
+        bound: Option<Box<dyn BoundAction>>,
+        action: Action,
+        actions: Vec<Action>,
+    ) -> Self {
+        Self {
+            method,
+            handler: Handler::Resource(bound, action, actions),
+            middlewares: Vec::new(),
+            name: None,
+            doc: None,
+            guards: Vec::new(),
+        }
     }
 
-    pub(crate) fn get_controller(&self) -> &Controller {
-        &self.controller
+    /// Adds a guard that accepts only the requests with the header. The name of the header is
+    /// case-insensitive.
+    ///
+    /// Guards let several endpoints share the same method and path. The router runs the first
+    /// endpoint whose guards all accept the request, preferring the media types that the
+    /// request prefers for [Endpoint::accepts]. If none of them does, the response is
+    /// `404 Not Found`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Endpoint, HttpMethod, Router, StatusCode };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// router.use_endpoint(
+    ///   "/users",
+    ///   Endpoint::new(HttpMethod::GET, |req, res| {
+    ///     res.status(StatusCode::Ok).body_text("v2");
+    ///   })
+    ///   .header_eq("x-api-version", "2"),
+    /// );
+    ///
+    /// router.get("/users", |req, res| {
+    ///   res.status(StatusCode::Ok).body_text("v1");
+    /// });
+    /// ```
+    pub fn header_eq(mut self, name: &str, value: &str) -> Self {
+        self.guards.push(Guard::header_eq(name, value));
+        self
     }
 
-    pub(crate) fn get_middlewares(&mut self) -> &mut Vec<Box<dyn Middleware>> {
-        &mut self.middlewares
+    /// Adds a guard that accepts only the requests with the query parameter. If none of the
+    /// endpoints accepts the request, the response is `404 Not Found`.
+    pub fn query_eq(mut self, key: &str, value: &str) -> Self {
+        self.guards.push(Guard::query_eq(key, value));
+        self
+    }
+
+    /// Adds a guard that accepts only the requests whose `Accept` header allows the media type
+    /// with a quality above zero, so `text/*;q=0` rejects `text/csv`. Requests without a valid
+    /// `Accept` header are accepted. If several endpoints accept the request, the one whose media
+    /// type has the highest quality is run, and the first one of them if their qualities are the
+    /// same. If none of the endpoints accepts the request, the response is
+    /// `406 Not Acceptable`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Endpoint, HttpMethod, Router, StatusCode, json::json };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// let json = Endpoint::new(HttpMethod::GET, |req, res| {
+    ///   res.status(StatusCode::Ok).body_json(json!({ "name": "John" }));
+    /// });
+    /// let text = Endpoint::new(HttpMethod::GET, |req, res| {
+    ///   res.status(StatusCode::Ok).body_text("John");
+    /// });
+    ///
+    /// router.use_endpoint("/user", json.accepts("application/json"));
+    /// router.use_endpoint("/user", text.accepts("text/plain"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the media type is not valid.
+    pub fn accepts(mut self, media_type: &str) -> Self {
+        self.guards.push(Guard::accepts(media_type));
+        self
+    }
+
+    /// Adds a guard that accepts only the requests whose `Content-Type` is the media type. The
+    /// parameters, such as `charset`, are ignored on both sides. If none of the endpoints accepts
+    /// the request, the response is `415 Unsupported Media Type`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the media type is not valid.
+    pub fn content_type(mut self, media_type: &str) -> Self {
+        self.guards.push(Guard::content_type(media_type));
+        self
+    }
+
+    /// Adds a guard that accepts only the requests for which the predicate returns `true`. If
+    /// none of the endpoints accepts the request, the response is `404 Not Found`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Endpoint, HttpMethod, Router, StatusCode };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// let endpoint = Endpoint::new(HttpMethod::GET, |req, res| {
+    ///   res.status(StatusCode::Ok);
+    /// })
+    /// .guard(|req| req.get_header("cookie").is_some());
+    ///
+    /// router.use_endpoint("/dashboard", endpoint);
+    /// ```
+    pub fn guard(mut self, predicate: impl Fn(&Request) -> bool + Send + Sync + 'static) -> Self {
+        self.guards
+            .push(Guard::new(StatusCode::NotFound, predicate));
+        self
+    }
+
+    /// Returns whether the endpoint is an action that its resource does not implement, which only
+    /// responds with `405 Method Not Allowed`.
+    pub(crate) fn is_placeholder(&self) -> bool {
+        matches!(self.handler, Handler::Resource(None, _, _))
+    }
+
+    pub(crate) fn get_guards(&self) -> &[Guard] {
+        &self.guards
+    }
+
+    pub(crate) fn get_method(&self) -> &HttpMethod {
+        &self.method
+    }
+
+    pub(crate) fn get_middleware_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.middlewares.iter().map(|middleware| middleware.name())
+    }
+
+    pub(crate) fn add_middleware(&mut self, middleware: Box<dyn Middleware>) {
+        self.middlewares.push(middleware);
+    }
+
+    pub(crate) fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub(crate) fn set_name(&mut self, name: &str) {
+        self.name = Some(name.to_string());
+    }
+
+    pub(crate) fn get_doc(&self) -> Option<&RouteDoc> {
+        self.doc.as_ref()
+    }
+
+    pub(crate) fn set_doc(&mut self, doc: RouteDoc) {
+        self.doc = Some(doc);
+    }
+
+    /// Runs the middlewares of the endpoint around the controller.
+    pub(crate) fn handle(&mut self, request: &Request, response: &mut Response) -> HandlerResult {
+        let Endpoint {
+            handler,
+            middlewares,
+            ..
+        } = self;
+
+        run_middlewares(middlewares, request, response, &mut |request, response| {
+            match handler {
+                Handler::Controller(controller) => controller(request, response),
+                Handler::Resource(bound, action, actions) => {
+                    action.run(bound, actions, request, response)
+                }
+            }
+
+            HandlerResult::Next
+        })
     }
 }
 
@@ -87,8 +275,18 @@ impl Clone for Endpoint {
     fn clone(&self) -> Self {
         Self {
             method: self.method.clone(),
-            controller: self.controller.clone(),
+            handler: self.handler.clone(),
             middlewares: self.middlewares.clone(),
+            name: self.name.clone(),
+            doc: self.doc.clone(),
+            guards: self.guards.clone(),
         }
     }
 }
+
+/// What an endpoint runs after its middlewares.
+#[derive(Debug, Clone)]
+enum Handler {
+    Controller(Controller),
+    Resource(Option<Box<dyn BoundAction>>, Action, Vec<Action>),
+}
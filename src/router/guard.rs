@@ -0,0 +1,108 @@
+use std::{
+    fmt::{Debug, Formatter, Result as fResult},
+    sync::Arc,
+};
+
+use crate::{MediaType, Request, StatusCode, headers::Accept};
+
+type Predicate = dyn Fn(&Request) -> bool + Send + Sync;
+
+/// A predicate that a request has to satisfy for an endpoint to handle it. `status` is the status
+/// of the response when no endpoint for the method and the path accepts the request.
+#[derive(Clone)]
+pub(crate) struct Guard {
+    predicate: Arc<Predicate>,
+    status: StatusCode,
+    /// The media type of an [Guard::accepts] guard
+    media_type: Option<MediaType>,
+}
+
+impl Guard {
+    pub(crate) fn new(
+        status: StatusCode,
+        predicate: impl Fn(&Request) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            predicate: Arc::new(predicate),
+            status,
+            media_type: None,
+        }
+    }
+
+    /// Accepts the requests with the header. The name is case-insensitive.
+    pub(crate) fn header_eq(name: &str, value: &str) -> Self {
+        let (name, value) = (name.to_string(), value.to_string());
+
+        Self::new(StatusCode::NotFound, move |request| {
+            request.get_header(&name) == Some(value.as_str())
+        })
+    }
+
+    /// Accepts the requests with the query parameter.
+    pub(crate) fn query_eq(key: &str, value: &str) -> Self {
+        let (key, value) = (key.to_string(), value.to_string());
+
+        Self::new(StatusCode::NotFound, move |request| {
+            request.get_query_param(&key) == Some(&value)
+        })
+    }
+
+    /// Accepts the requests whose `Accept` header gives the media type a quality above zero, or
+    /// that have no valid `Accept` header.
+    pub(crate) fn accepts(media_type: &str) -> Self {
+        let media_type = MediaType::try_from(media_type)
+            .unwrap_or_else(|_| panic!("Invalid media type: {media_type}"));
+
+        let guard_media_type = media_type.clone();
+        let mut guard = Self::new(StatusCode::NotAcceptable, move |request| {
+            Self::quality(request, &guard_media_type) > 0.0
+        });
+
+        guard.media_type = Some(media_type);
+        guard
+    }
+
+    /// Accepts the requests whose `Content-Type` is the media type, ignoring the parameters of
+    /// both.
+    pub(crate) fn content_type(media_type: &str) -> Self {
+        let media_type = MediaType::try_from(media_type)
+            .unwrap_or_else(|_| panic!("Invalid media type: {media_type}"));
+
+        Self::new(StatusCode::UnsupportedMediaType, move |request| {
+            request
+                .get_content_type()
+                .is_some_and(|content_type| content_type.essence() == media_type.essence())
+        })
+    }
+
+    /// Returns the quality that the request gives to the media type of an [Guard::accepts]
+    /// guard, and `None` for the other guards.
+    pub(crate) fn get_quality(&self, request: &Request) -> Option<f32> {
+        self.media_type
+            .as_ref()
+            .map(|media_type| Self::quality(request, media_type))
+    }
+
+    /// Returns the quality that the `Accept` header gives to the media type. Every media type has
+    /// the quality `1` if the request has no valid `Accept` header.
+    fn quality(request: &Request, media_type: &MediaType) -> f32 {
+        match request.typed_header::<Accept>() {
+            Ok(Some(accept)) => accept.get_quality(media_type),
+            _ => 1.0,
+        }
+    }
+
+    pub(crate) fn check(&self, request: &Request) -> bool {
+        (self.predicate)(request)
+    }
+
+    pub(crate) fn get_status(&self) -> StatusCode {
+        self.status
+    }
+}
+
+impl Debug for Guard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write!(f, "Guard({})", self.status)
+    }
+}
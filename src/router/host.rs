@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use crate::{
+    server::route_handler::{HandlerResult, RouteHandler},
+    Request, Response, StatusCode,
+};
+
+use super::Router;
+
+/// Dispatches the requests to different routers based on their `Host` header.
+///
+/// A host can be exact, e.g. `example.com`, or a wildcard such as `*.example.com` that matches
+/// every subdomain of `example.com`. The subdomain matched by a wildcard is added to the
+/// parameters of the request as `subdomain`. Exact hosts are tried before wildcards, and longer
+/// wildcards before shorter ones. The port of the `Host` header is ignored.
+///
+/// When the request target is in absolute-form, e.g. `GET http://example.com/ HTTP/1.1`, its
+/// host is used instead of the `Host` header, as required by RFC 9112.
+///
+/// Requests without a `Host` header or with an unknown host are sent to the default router. If
+/// there is no default router, they get `400 Bad Request`, which can be changed with
+/// [HostRouter::unknown_host_status].
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::{ HostRouter, Router, Server, StatusCode };
+///
+/// let mut server = Server::create();
+/// let mut hosts = HostRouter::new();
+///
+/// let mut main_router = Router::new();
+/// let mut tenant_router = Router::new();
+///
+/// main_router.get("/", |req, res| {
+///   res.status(StatusCode::Ok).body_text("Welcome");
+/// });
+///
+/// tenant_router.get("/", |req, res| {
+///   let tenant = req.get_param("subdomain").unwrap();
+///   res.status(StatusCode::Ok).body_text(tenant);
+/// });
+///
+/// hosts
+///   .host("example.com", main_router)
+///   .host("*.example.com", tenant_router)
+///   .unknown_host_status(StatusCode::NotFound);
+///
+/// server.use_handler(hosts);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HostRouter {
+    exact: HashMap<String, Router>,
+    wildcards: Vec<(String, Router)>,
+    default: Option<Router>,
+    unknown_host_status: StatusCode,
+}
+
+impl HostRouter {
+    /// Creates a new host router without any hosts
+    pub fn new() -> Self {
+        Self {
+            exact: HashMap::new(),
+            wildcards: Vec::new(),
+            default: None,
+            unknown_host_status: StatusCode::BadRequest,
+        }
+    }
+
+    /// Adds a router for a host. The host can start with `*.` to match its subdomains.
+    ///
+    /// The port of the host is ignored, as it is for the requests, so `example.com:8080` is the
+    /// same host as `example.com`. Adding a router for a host that already has one replaces it.
+    pub fn host(&mut self, host: &str, router: Router) -> &mut Self {
+        let host = Self::strip_port(host.trim()).to_lowercase();
+
+        match host.strip_prefix("*.") {
+            Some(suffix) => {
+                let suffix = format!(".{suffix}");
+
+                self.wildcards.retain(|(existing, _)| *existing != suffix);
+                self.wildcards.push((suffix, router));
+                self.wildcards
+                    .sort_by_key(|(suffix, _)| std::cmp::Reverse(suffix.len()));
+            }
+            None => {
+                self.exact.insert(host, router);
+            }
+        }
+
+        self
+    }
+
+    /// Sets the router for the requests without a `Host` header or with an unknown host
+    pub fn default_router(&mut self, router: Router) -> &mut Self {
+        self.default = Some(router);
+        self
+    }
+
+    /// Sets the status of the response for the requests without a `Host` header or with an
+    /// unknown host, when there is no default router. It is `400 Bad Request` by default.
+    pub fn unknown_host_status(&mut self, status_code: StatusCode) -> &mut Self {
+        self.unknown_host_status = status_code;
+        self
+    }
+
+    /// Returns the host without the port, in lowercase. The host of an absolute-form target
+    /// takes precedence over the `Host` header.
+    fn get_host(request: &Request) -> Option<String> {
+        let host = match request.get_authority() {
+            Some(authority) => authority.rsplit('@').next().unwrap_or_default(),
+            None => request.get_header("host")?.trim(),
+        };
+
+        let host = Self::strip_port(host);
+
+        (!host.is_empty()).then(|| host.to_lowercase())
+    }
+
+    /// Returns the host without the port. The brackets of an IPv6 address are kept.
+    fn strip_port(host: &str) -> &str {
+        match host.strip_prefix('[') {
+            Some(ipv6) => &host[..ipv6.find(']').map_or(host.len(), |end| end + 2)],
+            None => host.split(':').next().unwrap_or_default(),
+        }
+    }
+}
+
+impl RouteHandler for HostRouter {
+    fn compile(&mut self) {
+        self.exact.values_mut().for_each(Router::compile);
+        self.wildcards
+            .iter_mut()
+            .for_each(|(_, router)| router.compile());
+
+        if let Some(router) = &mut self.default {
+            router.compile();
+        }
+    }
+
+    fn handle(&mut self, request: &Request, response: &mut Response) -> HandlerResult {
+        let host = Self::get_host(request);
+
+        if let Some(router) = host.as_ref().and_then(|host| self.exact.get_mut(host)) {
+            return router.handle(request, response);
+        }
+
+        let wildcard = host.as_ref().and_then(|host| {
+            self.wildcards.iter_mut().find_map(|(suffix, router)| {
+                let subdomain = host.strip_suffix(suffix.as_str())?;
+                (!subdomain.is_empty()).then(|| (subdomain.to_string(), router))
+            })
+        });
+
+        if let Some((subdomain, router)) = wildcard {
+            let request = request.with_params([("subdomain".to_string(), subdomain)]);
+
+            return router.handle(&request, response);
+        }
+
+        match &mut self.default {
+            Some(router) => router.handle(request, response),
+            None => {
+                response.status(self.unknown_host_status);
+                HandlerResult::End
+            }
+        }
+    }
+}
+
+impl Default for HostRouter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,423 @@
+use crate::{
+    json::{json, JsonValue},
+    StatusCode,
+};
+
+use super::{Endpoint, PathType, Router};
+
+/// Documentation of an endpoint that is used to generate the OpenAPI document of a router.
+///
+/// It is added to the endpoint that was added right before it with [Router::doc].
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::{ Router, RouteDoc, StatusCode, json::json };
+///
+/// let mut router = Router::new();
+///
+/// router
+///   .get("/users/:id", |req, res| {
+///     res.status(StatusCode::Ok);
+///   })
+///   .doc(
+///     RouteDoc::new()
+///       .summary("Returns a user")
+///       .tag("users")
+///       .param("id", "Id of the user")
+///       .response(StatusCode::Ok, "The user", Some(json!({ "type": "object" })))
+///       .response(StatusCode::NotFound, "There is no user with the id", None),
+///   );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouteDoc {
+    summary: Option<String>,
+    description: Option<String>,
+    tags: Vec<String>,
+    params: Vec<ParamDoc>,
+    request_body: Option<JsonValue>,
+    responses: Vec<ResponseDoc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ParamDoc {
+    name: String,
+    location: &'static str,
+    description: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ResponseDoc {
+    status_code: StatusCode,
+    description: String,
+    schema: Option<JsonValue>,
+}
+
+impl RouteDoc {
+    /// Creates an empty documentation
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the summary of the endpoint
+    pub fn summary(&mut self, summary: &str) -> &mut Self {
+        self.summary = Some(summary.to_string());
+        self
+    }
+
+    /// Sets the description of the endpoint
+    pub fn description(&mut self, description: &str) -> &mut Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Adds a tag to the endpoint
+    pub fn tag(&mut self, tag: &str) -> &mut Self {
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    /// Adds a description to a path parameter of the endpoint
+    pub fn param(&mut self, name: &str, description: &str) -> &mut Self {
+        self.params.push(ParamDoc {
+            name: name.to_string(),
+            location: "path",
+            description: description.to_string(),
+        });
+        self
+    }
+
+    /// Adds an optional query parameter to the endpoint
+    pub fn query(&mut self, name: &str, description: &str) -> &mut Self {
+        self.params.push(ParamDoc {
+            name: name.to_string(),
+            location: "query",
+            description: description.to_string(),
+        });
+        self
+    }
+
+    /// Sets the JSON Schema of the JSON request body
+    pub fn request_body(&mut self, schema: JsonValue) -> &mut Self {
+        self.request_body = Some(schema);
+        self
+    }
+
+    /// Adds a response with the JSON Schema of its JSON body, if it has one
+    pub fn response(
+        &mut self,
+        status_code: StatusCode,
+        description: &str,
+        schema: Option<JsonValue>,
+    ) -> &mut Self {
+        self.responses.push(ResponseDoc {
+            status_code,
+            description: description.to_string(),
+            schema,
+        });
+        self
+    }
+}
+
+impl Router {
+    /// Adds documentation to the endpoint that was added right before it. It is used by
+    /// [Router::openapi].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the last call on the router did not add an endpoint, e.g. it mounted a router or
+    /// added a middleware, a fallback or a resource.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, RouteDoc, StatusCode };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// router
+    ///   .post("/users", |req, res| {
+    ///     res.status(StatusCode::Created);
+    ///   })
+    ///   .doc(RouteDoc::new().summary("Creates a user").tag("users"));
+    /// ```
+    pub fn doc(&mut self, doc: &RouteDoc) -> &mut Self {
+        let Some((path_types, index)) = self.last_endpoint.clone() else {
+            panic!("Error while adding documentation: No endpoint is added right before it");
+        };
+
+        self.get_branch_or_insert(path_types).endpoints[index].set_doc(doc.clone());
+        self
+    }
+
+    /// Generates an OpenAPI 3.1 document of the routes of the router tree.
+    ///
+    /// Parameters such as `:id` are documented as the path parameters of the operations. Routes
+    /// that differ only by the names of their parameters, e.g. `/users/:id` and `/users/:name`,
+    /// are documented under the same path with the names of the route that comes first. The
+    /// documentation added with [Router::doc] is used for the summaries, tags, parameters,
+    /// request bodies and responses, and the route names are used as the operation ids.
+    ///
+    /// Endpoints with guards that share a method and a path are documented as one operation. The
+    /// summary, description, operation id and request body are taken from the first endpoint that
+    /// has them, and the tags, parameters and responses of all of them are combined.
+    ///
+    /// The document can be served with
+    /// [ServeOpenApi](crate::middlewares::ServeOpenApi).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, StatusCode, json::json };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// router
+    ///   .get("/users/:id", |req, res| {
+    ///     res.status(StatusCode::Ok);
+    ///   })
+    ///   .name("user.show");
+    ///
+    /// let document = router.openapi("Users API", "1.0.0");
+    ///
+    /// assert_eq!(document["openapi"], json!("3.1.0"));
+    /// assert_eq!(document["paths"]["/users/{id}"]["get"]["operationId"], json!("user.show"));
+    /// ```
+    pub fn openapi(&self, title: &str, version: &str) -> JsonValue {
+        let mut paths = serde_json::Map::new();
+
+        self.collect_operations(&mut Vec::new(), &mut Vec::new(), &mut paths);
+
+        json!({
+            "openapi": "3.1.0",
+            "info": { "title": title, "version": version },
+            "paths": paths,
+        })
+    }
+
+    /// Adds the operations of the router tree to `paths`. `path` is the path of this router and
+    /// `templates` are the paths that are already documented.
+    fn collect_operations(
+        &self,
+        path: &mut Vec<PathType>,
+        templates: &mut Vec<Vec<PathType>>,
+        paths: &mut serde_json::Map<String, JsonValue>,
+    ) {
+        // Actions that a resource does not implement are not operations of the path
+        let endpoints = self
+            .endpoints
+            .iter()
+            .filter(|endpoint| !endpoint.is_placeholder())
+            .collect::<Vec<_>>();
+
+        if !endpoints.is_empty() && !templates.iter().any(|template| same_shape(template, path)) {
+            templates.push(path.clone());
+        }
+
+        for endpoint in endpoints {
+            let Some(template) = templates.iter().find(|template| same_shape(template, path))
+            else {
+                continue;
+            };
+
+            let item = paths
+                .entry(openapi_path(template))
+                .or_insert_with(|| json!({}));
+            let method = endpoint.get_method().to_string().to_lowercase();
+
+            let operation = operation(path, template, endpoint);
+
+            match item.get_mut(&method) {
+                Some(existing) => merge_operation(existing, operation),
+                None => item[method] = operation,
+            }
+        }
+
+        let subdirs = self
+            .subdirs
+            .iter()
+            .map(|(subdir, router)| (PathType::Subdirectory(subdir.clone()), router));
+        let param_dirs = self
+            .param_dirs
+            .iter()
+            .map(|(param, router)| (PathType::Parameter(param.clone()), router));
+
+        for (path_type, router) in subdirs.chain(param_dirs) {
+            path.push(path_type);
+            router.collect_operations(path, templates, paths);
+            path.pop();
+        }
+
+        for router in &self.mounted {
+            router.collect_operations(path, templates, paths);
+        }
+    }
+}
+
+/// Merges the operation of an endpoint that shares its method and path with an earlier one,
+/// keeping the fields that the earlier operation already has.
+fn merge_operation(operation: &mut JsonValue, other: JsonValue) {
+    let (JsonValue::Object(operation), JsonValue::Object(other)) = (operation, other) else {
+        return;
+    };
+
+    for (key, value) in other {
+        let Some(existing) = operation.get_mut(&key) else {
+            operation.insert(key, value);
+            continue;
+        };
+
+        match (key.as_str(), existing, value) {
+            ("tags", JsonValue::Array(tags), JsonValue::Array(others)) => {
+                for tag in others {
+                    if !tags.contains(&tag) {
+                        tags.push(tag);
+                    }
+                }
+            }
+            ("parameters", JsonValue::Array(params), JsonValue::Array(others)) => {
+                for param in others {
+                    let exists = params.iter().any(|existing| {
+                        existing["name"] == param["name"] && existing["in"] == param["in"]
+                    });
+
+                    if !exists {
+                        params.push(param);
+                    }
+                }
+            }
+            ("responses", JsonValue::Object(responses), JsonValue::Object(others)) => {
+                for (status, response) in others {
+                    responses.entry(status).or_insert(response);
+                }
+
+                if responses.len() > 1 {
+                    responses.remove("default");
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the path in the OpenAPI format, e.g. `/users/{id}`.
+fn openapi_path(path_types: &[PathType]) -> String {
+    let path = path_types
+        .iter()
+        .map(|path_type| match path_type {
+            PathType::Subdirectory(subdir) => format!("/{subdir}"),
+            PathType::Parameter(param) => format!("/{{{param}}}"),
+        })
+        .collect::<String>();
+
+    if path.is_empty() {
+        "/".to_string()
+    } else {
+        path
+    }
+}
+
+/// Checks if the paths are the same except for the names of their parameters.
+fn same_shape(left: &[PathType], right: &[PathType]) -> bool {
+    left.len() == right.len()
+        && left.iter().zip(right).all(|pair| match pair {
+            (PathType::Subdirectory(left), PathType::Subdirectory(right)) => left == right,
+            (PathType::Parameter(_), PathType::Parameter(_)) => true,
+            _ => false,
+        })
+}
+
+/// Returns the OpenAPI operation object of the endpoint. The path parameters are named after the
+/// `template` that the path of the endpoint is documented under.
+fn operation(path_types: &[PathType], template: &[PathType], endpoint: &Endpoint) -> JsonValue {
+    let default_doc = RouteDoc::default();
+    let doc = endpoint.get_doc().unwrap_or(&default_doc);
+
+    let path_params = path_types.iter().zip(template).filter_map(|pair| {
+        let (PathType::Parameter(name), PathType::Parameter(template_name)) = pair else {
+            return None;
+        };
+
+        let mut param = json!({
+            "name": template_name,
+            "in": "path",
+            "required": true,
+            "schema": { "type": "string" },
+        });
+
+        let param_doc = doc
+            .params
+            .iter()
+            .find(|param| param.location == "path" && &param.name == name);
+
+        if let Some(param_doc) = param_doc {
+            param["description"] = json!(param_doc.description);
+        }
+
+        Some(param)
+    });
+    let query_params = doc
+        .params
+        .iter()
+        .filter(|param| param.location == "query")
+        .map(|param| {
+            json!({
+                "name": param.name,
+                "in": "query",
+                "required": false,
+                "description": param.description,
+                "schema": { "type": "string" },
+            })
+        });
+    let parameters = path_params.chain(query_params).collect::<Vec<JsonValue>>();
+
+    let mut responses = serde_json::Map::new();
+
+    for response in &doc.responses {
+        let mut value = json!({ "description": response.description });
+
+        if let Some(schema) = &response.schema {
+            value["content"] = json!({ "application/json": { "schema": schema } });
+        }
+
+        responses.insert(u16::from(&response.status_code).to_string(), value);
+    }
+
+    if responses.is_empty() {
+        responses.insert(
+            "default".to_string(),
+            json!({ "description": "Default response" }),
+        );
+    }
+
+    let mut operation = json!({ "responses": responses });
+
+    if let Some(name) = endpoint.get_name() {
+        operation["operationId"] = json!(name);
+    }
+
+    if let Some(summary) = &doc.summary {
+        operation["summary"] = json!(summary);
+    }
+
+    if let Some(description) = &doc.description {
+        operation["description"] = json!(description);
+    }
+
+    if !doc.tags.is_empty() {
+        operation["tags"] = json!(doc.tags);
+    }
+
+    if !parameters.is_empty() {
+        operation["parameters"] = json!(parameters);
+    }
+
+    if let Some(schema) = &doc.request_body {
+        operation["requestBody"] = json!({
+            "required": true,
+            "content": { "application/json": { "schema": schema } },
+        });
+    }
+
+    operation
+}
@@ -0,0 +1,332 @@
+use std::fmt::{Debug, Formatter, Result as fResult};
+
+/// Maximum number of parameters a single route can have.
+pub(crate) const MAX_PARAMS: usize = 16;
+
+/// A compressed radix tree that maps route patterns such as `/users/:id/posts` to values.
+///
+/// Static parts of the patterns are stored as shared prefixes and every parameter is a node that
+/// matches a single path segment. Static parts can be inserted as case-insensitive, in which case
+/// they are kept in separate nodes that match ASCII letters in any case. The tree is built once and
+/// looking up a path does not allocate.
+pub(crate) struct RadixTree<T> {
+    root: Node<T>,
+}
+
+struct Node<T> {
+    kind: NodeKind,
+    ignore_case: bool,
+    children: Vec<Node<T>>,
+    param_child: Option<Box<Node<T>>>,
+    leaves: Vec<Leaf<T>>,
+}
+
+enum NodeKind {
+    Static(String),
+    Parameter,
+}
+
+struct Leaf<T> {
+    param_names: Vec<String>,
+    value: T,
+}
+
+/// A part of a route pattern. Static parts that ignore the case are lowercased.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token<'a> {
+    Static(String, bool),
+    Parameter(&'a str),
+}
+
+/// Parameters captured while looking up a path.
+///
+/// Names are borrowed from the tree and values are borrowed from the looked up path.
+pub(crate) struct Params<'t, 'p> {
+    names: &'t [String],
+    values: [&'p str; MAX_PARAMS],
+}
+
+impl<T> RadixTree<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            root: Node::new(NodeKind::Static(String::new()), false),
+        }
+    }
+
+    /// Inserts a value for a pattern such as `/users/:id/posts`.
+    ///
+    /// `ignore_case` has a value for every segment of the pattern. The static segments whose value
+    /// is `true` match ASCII letters in any case, and the others match only the exact text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern has more than [MAX_PARAMS] parameters.
+    pub(crate) fn insert(&mut self, pattern: &str, ignore_case: &[bool], value: T) {
+        let tokens = tokenize(pattern, ignore_case);
+        let param_names = tokens
+            .iter()
+            .filter_map(|token| match token {
+                Token::Parameter(name) => Some(name.to_string()),
+                Token::Static(..) => None,
+            })
+            .collect::<Vec<String>>();
+
+        if param_names.len() > MAX_PARAMS {
+            panic!("Routes can not have more than {MAX_PARAMS} parameters.");
+        }
+
+        let leaf = Leaf { param_names, value };
+
+        self.root.insert("", false, &tokens, leaf);
+    }
+
+    /// Looks up the path and returns the value with the highest rank with the captured
+    /// parameters. `rank` returns `None` for the values that are not accepted, and the first
+    /// value wins if several of them have the same rank.
+    ///
+    /// Static children are tried before the parameter child. If a branch can not complete the
+    /// match, the lookup backtracks into the next candidate.
+    pub(crate) fn find<'t, 'p, R: PartialOrd>(
+        &'t self,
+        path: &'p str,
+        rank: impl Fn(&T) -> Option<R>,
+    ) -> Option<(&'t T, Params<'t, 'p>)> {
+        let mut values: [&'p str; MAX_PARAMS] = [""; MAX_PARAMS];
+
+        let leaf = self.root.find(path, &mut values, 0, &rank)?;
+        let params = Params {
+            names: &leaf.param_names,
+            values,
+        };
+
+        Some((&leaf.value, params))
+    }
+}
+
+impl<T> Node<T> {
+    fn new(kind: NodeKind, ignore_case: bool) -> Self {
+        Self {
+            kind,
+            ignore_case,
+            children: Vec::new(),
+            param_child: None,
+            leaves: Vec::new(),
+        }
+    }
+
+    /// Inserts the leaf below this node. `pending` is the static text that has not been consumed
+    /// yet from the first token and `ignore_case` is whether that token ignores the case.
+    fn insert(&mut self, pending: &str, ignore_case: bool, tokens: &[Token<'_>], leaf: Leaf<T>) {
+        if pending.is_empty() {
+            return match tokens.split_first() {
+                None => self.leaves.push(leaf),
+                Some((Token::Static(text, ignore_case), rest)) => {
+                    self.insert(text, *ignore_case, rest, leaf)
+                }
+                Some((Token::Parameter(_), rest)) => self
+                    .param_child
+                    .get_or_insert_with(|| Box::new(Node::new(NodeKind::Parameter, false)))
+                    .insert("", false, rest, leaf),
+            };
+        }
+
+        let found = self.children.iter().position(|child| {
+            child.ignore_case == ignore_case
+                && child.prefix().chars().next() == pending.chars().next()
+        });
+
+        let Some(index) = found else {
+            let mut child = Node::new(NodeKind::Static(pending.to_string()), ignore_case);
+            child.insert("", false, tokens, leaf);
+            self.children.push(child);
+            return;
+        };
+
+        let child = &mut self.children[index];
+        let common = common_prefix_len(child.prefix(), pending);
+
+        if common < child.prefix().len() {
+            child.split_at(common);
+        }
+
+        child.insert(&pending[common..], ignore_case, tokens, leaf);
+    }
+
+    /// Splits the static prefix of the node so the node keeps only the first `index` bytes.
+    fn split_at(&mut self, index: usize) {
+        let NodeKind::Static(prefix) = &mut self.kind else {
+            return;
+        };
+
+        let suffix = prefix.split_off(index);
+        let mut lower = Node::new(NodeKind::Static(suffix), self.ignore_case);
+
+        std::mem::swap(&mut lower.children, &mut self.children);
+        std::mem::swap(&mut lower.param_child, &mut self.param_child);
+        std::mem::swap(&mut lower.leaves, &mut self.leaves);
+
+        self.children.push(lower);
+    }
+
+    fn prefix(&self) -> &str {
+        match &self.kind {
+            NodeKind::Static(prefix) => prefix,
+            NodeKind::Parameter => "",
+        }
+    }
+
+    fn find<'t, 'p, R: PartialOrd>(
+        &'t self,
+        path: &'p str,
+        values: &mut [&'p str; MAX_PARAMS],
+        depth: usize,
+        rank: &impl Fn(&T) -> Option<R>,
+    ) -> Option<&'t Leaf<T>> {
+        let (rest, depth) = match &self.kind {
+            NodeKind::Static(prefix) if self.ignore_case => {
+                let head = path.get(..prefix.len())?;
+
+                if !head.eq_ignore_ascii_case(prefix) {
+                    return None;
+                }
+
+                (&path[prefix.len()..], depth)
+            }
+            NodeKind::Static(prefix) => (path.strip_prefix(prefix.as_str())?, depth),
+            NodeKind::Parameter => {
+                let end = path.find('/').unwrap_or(path.len());
+
+                if end == 0 || depth >= MAX_PARAMS {
+                    return None;
+                }
+
+                values[depth] = &path[..end];
+                (&path[end..], depth + 1)
+            }
+        };
+
+        if rest.is_empty() {
+            return self
+                .leaves
+                .iter()
+                .filter_map(|leaf| Some((rank(&leaf.value)?, leaf)))
+                .fold(None, |best, (rank, leaf)| match best {
+                    Some((ref best_rank, _)) if *best_rank >= rank => best,
+                    _ => Some((rank, leaf)),
+                })
+                .map(|(_, leaf)| leaf);
+        }
+
+        let first_byte = rest.as_bytes()[0];
+
+        let static_match = self
+            .children
+            .iter()
+            .filter(|child| match child.ignore_case {
+                true => child.prefix().as_bytes()[0] == first_byte.to_ascii_lowercase(),
+                false => child.prefix().as_bytes()[0] == first_byte,
+            })
+            .find_map(|child| child.find(rest, values, depth, rank));
+
+        if static_match.is_some() {
+            return static_match;
+        }
+
+        self.param_child
+            .as_ref()
+            .and_then(|child| child.find(rest, values, depth, rank))
+    }
+}
+
+impl<'t, 'p> Params<'t, 'p> {
+    /// Returns an iterator over the parameter names and values.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&'t str, &'p str)> + '_ {
+        self.names
+            .iter()
+            .zip(self.values.iter())
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+}
+
+/// Splits a pattern into static parts and parameters. A parameter is a segment that starts with
+/// `:`. Consecutive static segments are joined if they have the same `ignore_case` value, and the
+/// slashes, which have no case, are joined to the static part before them.
+fn tokenize<'a>(pattern: &'a str, ignore_case: &[bool]) -> Vec<Token<'a>> {
+    let mut tokens = Vec::new();
+    let segments = pattern.split('/').filter(|segment| !segment.is_empty());
+
+    for (index, segment) in segments.enumerate() {
+        let ignore_case = ignore_case.get(index).copied().unwrap_or(false);
+        push_static(&mut tokens, "/", ignore_case);
+
+        match segment.strip_prefix(':') {
+            Some(param) => tokens.push(Token::Parameter(param)),
+            None => push_static(&mut tokens, segment, ignore_case),
+        }
+    }
+
+    if tokens.is_empty() {
+        tokens.push(Token::Static("/".to_string(), false));
+    }
+
+    tokens
+}
+
+/// Adds static text to the tokens, joining it to the last token if it can be.
+fn push_static(tokens: &mut Vec<Token<'_>>, text: &str, ignore_case: bool) {
+    let text = match ignore_case {
+        true => text.to_ascii_lowercase(),
+        false => text.to_string(),
+    };
+
+    match tokens.last_mut() {
+        Some(Token::Static(last, last_ignore_case))
+            if *last_ignore_case == ignore_case || text == "/" =>
+        {
+            last.push_str(&text)
+        }
+        _ => tokens.push(Token::Static(text, ignore_case)),
+    }
+}
+
+fn common_prefix_len(left: &str, right: &str) -> usize {
+    left.char_indices()
+        .zip(right.chars())
+        .take_while(|((_, l), r)| l == r)
+        .last()
+        .map(|((index, c), _)| index + c.len_utf8())
+        .unwrap_or(0)
+}
+
+impl<T> Debug for RadixTree<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        fn write_node<T>(f: &mut Formatter<'_>, node: &Node<T>, indent: usize) -> fResult {
+            let label = match &node.kind {
+                NodeKind::Static(prefix) if node.ignore_case => format!("{prefix:?} (ignore case)"),
+                NodeKind::Static(prefix) => format!("{prefix:?}"),
+                NodeKind::Parameter => ":".to_string(),
+            };
+
+            writeln!(f, "{:indent$}{label} ({} leaves)", "", node.leaves.len())?;
+
+            for child in &node.children {
+                write_node(f, child, indent + 2)?;
+            }
+
+            if let Some(child) = &node.param_child {
+                write_node(f, child, indent + 2)?;
+            }
+
+            Ok(())
+        }
+
+        writeln!(f, "RadixTree")?;
+        write_node(f, &self.root, 2)
+    }
+}
+
+impl<T> Default for RadixTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
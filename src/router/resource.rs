@@ -0,0 +1,287 @@
+use std::fmt::{Debug, Formatter, Result as fResult};
+
+use dyn_clone::DynClone;
+
+use crate::{Request, Response, StatusCode};
+
+use super::{Endpoint, HttpMethod, PathType, Router};
+
+/// A RESTful resource that is added to a router with [Router::resource].
+///
+/// Every action is optional. An action returns the function that handles it, and the actions that
+/// return `None`, as they do unless the resource overrides them, respond with
+/// `405 Method Not Allowed`. The `Allow` header of the response lists the methods of the
+/// implemented actions for the path, and is left out if there are none. The routes at `/:id` are
+/// only added if the resource implements one of their actions. The actions are read once when the
+/// resource is added to a router.
+///
+/// | Action    | Route               |
+/// |-----------|---------------------|
+/// | `index`   | `GET /users`        |
+/// | `create`  | `POST /users`       |
+/// | `show`    | `GET /users/:id`    |
+/// | `update`  | `PUT /users/:id`    |
+/// | `patch`   | `PATCH /users/:id`  |
+/// | `destroy` | `DELETE /users/:id` |
+///
+/// # Example
+///
+/// ```rust
+/// use krustie::{ Request, Resource, ResourceAction, Response, Router, StatusCode };
+///
+/// #[derive(Clone)]
+/// struct Users;
+///
+/// impl Users {
+///   fn list(&mut self, request: &Request, response: &mut Response) {
+///     response.status(StatusCode::Ok).body_text("users");
+///   }
+///
+///   fn get(&mut self, request: &Request, response: &mut Response) {
+///     response.status(StatusCode::Ok).body_text(request.get_param("id").unwrap());
+///   }
+/// }
+///
+/// impl Resource for Users {
+///   fn index(&self) -> Option<ResourceAction<Self>> {
+///     Some(Self::list)
+///   }
+///
+///   fn show(&self) -> Option<ResourceAction<Self>> {
+///     Some(Self::get)
+///   }
+/// }
+///
+/// let mut router = Router::new();
+///
+/// router.resource("/users", Users);
+/// ```
+pub trait Resource: Clone + Send + 'static {
+    /// Lists the resources, `GET /users`
+    fn index(&self) -> Option<ResourceAction<Self>> {
+        None
+    }
+
+    /// Returns a resource, `GET /users/:id`
+    fn show(&self) -> Option<ResourceAction<Self>> {
+        None
+    }
+
+    /// Creates a resource, `POST /users`
+    fn create(&self) -> Option<ResourceAction<Self>> {
+        None
+    }
+
+    /// Replaces a resource, `PUT /users/:id`
+    fn update(&self) -> Option<ResourceAction<Self>> {
+        None
+    }
+
+    /// Partially updates a resource, `PATCH /users/:id`
+    fn patch(&self) -> Option<ResourceAction<Self>> {
+        None
+    }
+
+    /// Deletes a resource, `DELETE /users/:id`
+    fn destroy(&self) -> Option<ResourceAction<Self>> {
+        None
+    }
+}
+
+/// The function that handles an action of a [Resource]. It is run with the resource that is
+/// added to the router.
+pub type ResourceAction<R> = fn(&mut R, &Request, &mut Response);
+
+/// An action of a resource that is bound to a clone of the resource, so that an endpoint can run
+/// it without knowing the type of the resource.
+pub(crate) trait BoundAction: DynClone + Send {
+    fn run(&mut self, request: &Request, response: &mut Response);
+}
+
+#[derive(Clone)]
+struct Bound<R> {
+    resource: R,
+    action: ResourceAction<R>,
+}
+
+impl<R: Resource> BoundAction for Bound<R> {
+    fn run(&mut self, request: &Request, response: &mut Response) {
+        (self.action)(&mut self.resource, request, response);
+    }
+}
+
+fn method_not_allowed(response: &mut Response) {
+    response.status(StatusCode::MethodNotAllowed);
+}
+
+/// An action of a [Resource].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Action {
+    /// [Resource::index], `GET /users`
+    Index,
+    /// [Resource::show], `GET /users/:id`
+    Show,
+    /// [Resource::create], `POST /users`
+    Create,
+    /// [Resource::update], `PUT /users/:id`
+    Update,
+    /// [Resource::patch], `PATCH /users/:id`
+    Patch,
+    /// [Resource::destroy], `DELETE /users/:id`
+    Destroy,
+}
+
+impl Action {
+    /// Every action with its method and whether it is run for a single resource at `/:id`.
+    const ALL: [(Action, HttpMethod, bool); 6] = [
+        (Action::Index, HttpMethod::GET, false),
+        (Action::Create, HttpMethod::POST, false),
+        (Action::Show, HttpMethod::GET, true),
+        (Action::Update, HttpMethod::PUT, true),
+        (Action::Patch, HttpMethod::PATCH, true),
+        (Action::Destroy, HttpMethod::DELETE, true),
+    ];
+
+    /// Runs the bound action, or responds with `405 Method Not Allowed` if the resource does not
+    /// implement it. The `Allow` header of a `405 Method Not Allowed` response lists the methods
+    /// of the other implemented `actions` for the same path, unless there are none.
+    pub(crate) fn run(
+        &self,
+        bound: &mut Option<Box<dyn BoundAction>>,
+        actions: &[Action],
+        request: &Request,
+        response: &mut Response,
+    ) {
+        match bound {
+            Some(bound) => bound.run(request, response),
+            None => method_not_allowed(response),
+        }
+
+        if response.get_status() == StatusCode::MethodNotAllowed
+            && response.get_header("Allow").is_none()
+        {
+            let single = self.is_single();
+            let allow = Action::ALL
+                .iter()
+                .filter(|(action, _, is_single)| {
+                    *is_single == single && action != self && actions.contains(action)
+                })
+                .map(|(_, method, _)| method.to_string())
+                .collect::<Vec<String>>();
+
+            if !allow.is_empty() {
+                response.set_header("Allow", &allow.join(", "));
+            }
+        }
+    }
+
+    fn is_single(&self) -> bool {
+        Action::ALL
+            .iter()
+            .any(|(action, _, single)| action == self && *single)
+    }
+}
+
+impl Router {
+    /// Adds the routes of a [Resource] to the path. The parameter of a single resource is `id`.
+    ///
+    /// Nested resources can be added with a parameter in the path, e.g.
+    /// `/users/:user_id/posts`. The parameter can not be named `id`, as it would be replaced by
+    /// the `id` of the nested resource.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path is invalid, has a parameter named `id` while the resource has a route at
+    /// `/:id`, or one of the routes already exists. No route is added if it panics.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Request, Resource, ResourceAction, Response, Router, StatusCode };
+    ///
+    /// #[derive(Clone)]
+    /// struct Posts;
+    ///
+    /// impl Resource for Posts {
+    ///   fn show(&self) -> Option<ResourceAction<Self>> {
+    ///     Some(|_, request, response| {
+    ///       let user_id = request.get_param("user_id").unwrap();
+    ///       let id = request.get_param("id").unwrap();
+    ///
+    ///       response.status(StatusCode::Ok).body_text(&format!("{user_id}/{id}"));
+    ///     })
+    ///   }
+    /// }
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// router.resource("/users/:user_id/posts", Posts);
+    /// ```
+    pub fn resource(&mut self, path: &str, resource: impl Resource) -> &mut Self {
+        let path_types = match Self::try_get_path_types(path.trim()) {
+            Ok(path_types) => path_types,
+            Err(err) => panic!("Error while adding resource: {err}"),
+        };
+
+        let routes = Action::ALL.map(|(action, method, single)| {
+            let mut path_types = path_types.clone();
+
+            if single {
+                path_types.push(PathType::Parameter("id".to_string()));
+            }
+
+            let bound = match action {
+                Action::Index => resource.index(),
+                Action::Show => resource.show(),
+                Action::Create => resource.create(),
+                Action::Update => resource.update(),
+                Action::Patch => resource.patch(),
+                Action::Destroy => resource.destroy(),
+            }
+            .map(|action| {
+                let resource = resource.clone();
+                Box::new(Bound { resource, action }) as Box<dyn BoundAction>
+            });
+
+            (action, method, path_types, bound)
+        });
+
+        let actions = routes
+            .iter()
+            .filter(|(_, _, _, bound)| bound.is_some())
+            .map(|(action, _, _, _)| *action)
+            .collect::<Vec<Action>>();
+
+        // The member routes at `/:id` are only added if the resource has one of their actions
+        let has_member = actions.iter().any(Action::is_single);
+        let routes = routes
+            .into_iter()
+            .filter(|(action, _, _, _)| has_member || !action.is_single())
+            .collect::<Vec<_>>();
+
+        for (_, method, path_types, _) in &routes {
+            if let Err(err) = self.check_route(path_types, method, false) {
+                panic!("Error while adding resource: {err}");
+            }
+        }
+
+        for (action, method, path_types, bound) in routes {
+            let endpoint = Endpoint::from_resource(method, bound, action, actions.clone());
+
+            if let Err(err) = self.add_endpoint(path_types, endpoint) {
+                panic!("Error while adding resource: {err}");
+            }
+        }
+
+        self.last_endpoint = None;
+        self
+    }
+}
+
+impl Debug for dyn BoundAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fResult {
+        write!(f, "BoundAction")
+    }
+}
+
+dyn_clone::clone_trait_object!(BoundAction);
@@ -0,0 +1,252 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::{Branch, HttpMethod, PathType, Router};
+
+impl Router {
+    /// Returns the routes of the router tree, sorted by their paths.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, StatusCode, HttpMethod, middlewares::GzipEncoder };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// router
+    ///   .use_middleware(GzipEncoder)
+    ///   .get("/users/:id", |req, res| {
+    ///     res.status(StatusCode::Ok);
+    ///   })
+    ///   .name("user.show");
+    ///
+    /// let routes = router.routes();
+    /// let route = &routes.get_routes()[0];
+    ///
+    /// assert_eq!(route.get_method(), &HttpMethod::GET);
+    /// assert_eq!(route.get_path(), "/users/:id");
+    /// assert_eq!(route.get_name(), Some("user.show"));
+    /// assert_eq!(route.get_middlewares(), ["GzipEncoder"]);
+    ///
+    /// println!("{routes}");
+    /// ```
+    pub fn routes(&self) -> RouteTable {
+        let mut routes = Vec::new();
+
+        self.collect_route_info(&mut Vec::new(), &mut Vec::new(), &mut routes);
+
+        let mut routes = routes
+            .into_iter()
+            .map(|(branches, mut route)| {
+                let mut middlewares = Vec::new();
+                self.collect_middleware_names(&branches, &mut middlewares);
+                middlewares.append(&mut route.middlewares);
+                route.middlewares = middlewares;
+                route
+            })
+            .collect::<Vec<RouteInfo>>();
+
+        routes.sort_by(|left, right| {
+            (&left.path, left.method.to_string()).cmp(&(&right.path, right.method.to_string()))
+        });
+
+        RouteTable { routes }
+    }
+
+    /// Collects the routes of the router tree with the branches to their routers. `path` is the
+    /// path of this router and the middlewares of the collected routes are only the middlewares
+    /// of their endpoints. The actions that a resource does not implement are skipped.
+    fn collect_route_info(
+        &self,
+        path: &mut Vec<PathType>,
+        branches: &mut Vec<Branch>,
+        routes: &mut Vec<(Vec<Branch>, RouteInfo)>,
+    ) {
+        let endpoints = self
+            .endpoints
+            .iter()
+            .filter(|endpoint| !endpoint.is_placeholder());
+
+        for endpoint in endpoints {
+            let route = RouteInfo {
+                method: endpoint.get_method().clone(),
+                path: PathType::to_path(path),
+                middlewares: endpoint.get_middleware_names().collect(),
+                name: endpoint.get_name().map(String::from),
+            };
+
+            routes.push((branches.clone(), route));
+        }
+
+        for (index, (subdir, router)) in self.subdirs.iter().enumerate() {
+            path.push(PathType::Subdirectory(subdir.clone()));
+            branches.push(Branch::Subdirectory(index));
+            router.collect_route_info(path, branches, routes);
+            branches.pop();
+            path.pop();
+        }
+
+        for (index, (param, router)) in self.param_dirs.iter().enumerate() {
+            path.push(PathType::Parameter(param.clone()));
+            branches.push(Branch::Parameter(index));
+            router.collect_route_info(path, branches, routes);
+            branches.pop();
+            path.pop();
+        }
+
+        for (index, router) in self.mounted.iter().enumerate() {
+            branches.push(Branch::Mounted(index));
+            router.collect_route_info(path, branches, routes);
+            branches.pop();
+        }
+    }
+
+    /// Collects the names of the middlewares of the routers on the branches in the order they
+    /// run, as [Router::dispatch] runs them.
+    fn collect_middleware_names(&self, branches: &[Branch], names: &mut Vec<&'static str>) {
+        names.extend(self.middlewares.iter().map(|middleware| middleware.name()));
+
+        let Some((branch, rest)) = branches.split_first() else {
+            return;
+        };
+
+        let (mut subdirs, mut any_param) = (&self.subdirs, &self.any_param);
+
+        for branch in self.find_through(branch, rest) {
+            let found = match branch {
+                Branch::Subdirectory(index) => subdirs.get(index).map(|(_, router)| router),
+                Branch::AnyParameter => any_param.as_ref(),
+                Branch::Parameter(_) | Branch::Mounted(_) => None,
+            };
+
+            let Some(found) = found else {
+                break;
+            };
+
+            names.extend(found.middlewares.iter().map(|middleware| middleware.name()));
+            (subdirs, any_param) = (&found.subdirs, &found.any_param);
+        }
+
+        let router = match branch {
+            Branch::Subdirectory(index) => self.subdirs.get(*index).map(|(_, router)| &**router),
+            Branch::Parameter(index) => self.param_dirs.get(*index).map(|(_, router)| &**router),
+            Branch::Mounted(index) => self.mounted.get(*index),
+            Branch::AnyParameter => None,
+        };
+
+        if let Some(router) = router {
+            router.collect_middleware_names(rest, names);
+        }
+    }
+}
+
+/// Routes of a router tree, returned by [Router::routes].
+///
+/// It is displayed as a table that can be logged when the server starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteTable {
+    routes: Vec<RouteInfo>,
+}
+
+impl RouteTable {
+    /// Returns the routes of the table
+    pub fn get_routes(&self) -> &[RouteInfo] {
+        &self.routes
+    }
+}
+
+/// A route of a router tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteInfo {
+    method: HttpMethod,
+    path: String,
+    middlewares: Vec<&'static str>,
+    name: Option<String>,
+}
+
+impl RouteInfo {
+    /// Returns the method of the route
+    pub fn get_method(&self) -> &HttpMethod {
+        &self.method
+    }
+
+    /// Returns the full path pattern of the route, e.g. `/users/:id`
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the names of the middlewares that run for the route, in the order they run. The
+    /// middlewares of the routers come before the middlewares of the endpoint.
+    pub fn get_middlewares(&self) -> &[&'static str] {
+        &self.middlewares
+    }
+
+    /// Returns the name of the route, if it is named with [Router::name]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+impl Display for RouteTable {
+    /// Formats the routes as a table
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, StatusCode };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// router
+    ///   .get("/", |req, res| {
+    ///     res.status(StatusCode::Ok);
+    ///   })
+    ///   .name("home")
+    ///   .post("/users", |req, res| {
+    ///     res.status(StatusCode::Created);
+    ///   });
+    ///
+    /// assert_eq!(
+    ///   router.routes().to_string(),
+    ///   "METHOD  PATH    NAME  MIDDLEWARES\n\
+    ///    GET     /       home\n\
+    ///    POST    /users"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let header = ["METHOD", "PATH", "NAME", "MIDDLEWARES"].map(String::from);
+        let rows = self.routes.iter().map(|route| {
+            [
+                route.method.to_string(),
+                route.path.clone(),
+                route.name.clone().unwrap_or_default(),
+                route.middlewares.join(", "),
+            ]
+        });
+        let rows = std::iter::once(header).chain(rows).collect::<Vec<_>>();
+
+        let mut widths = [0; 3];
+
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+
+        let lines = rows.iter().map(|row| {
+            let line = format!(
+                "{:w0$}  {:w1$}  {:w2$}  {}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+            );
+
+            line.trim_end().to_string()
+        });
+
+        write!(f, "{}", lines.collect::<Vec<String>>().join("\n"))
+    }
+}
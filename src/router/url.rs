@@ -0,0 +1,223 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::{PathType, Router};
+
+impl Router {
+    /// Names the endpoint that was added right before it, so its URL can be built with
+    /// [Router::url_for].
+    ///
+    /// Names are unique in a router tree. Routers mounted with [Router::use_router] keep the names
+    /// of their endpoints.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is already used, or if the last call on the router did not add an
+    /// endpoint, e.g. it mounted a router or added a middleware, a fallback or a resource.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, StatusCode };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// router
+    ///   .get("/users/:id", |req, res| {
+    ///     res.status(StatusCode::Ok);
+    ///   })
+    ///   .name("user.show");
+    /// ```
+    pub fn name(&mut self, name: &str) -> &mut Self {
+        let Some((path_types, index)) = self.last_endpoint.clone() else {
+            panic!("Error while naming route: No endpoint is added right before {name}");
+        };
+
+        if let Some(path_types) = self.find_named(name) {
+            let path = PathType::to_path(&path_types);
+            panic!("Error while naming route: {name} is already used for {path}");
+        }
+
+        self.get_branch_or_insert(path_types).endpoints[index].set_name(name);
+        self
+    }
+
+    /// Builds the URL of a named route.
+    ///
+    /// Every parameter of the route must be given. Parameters that are not in the route are added
+    /// to the query in the given order. Values are percent-encoded.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [UrlError] if there is no route with the name or a parameter of the route is
+    /// missing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ Router, StatusCode, UrlError };
+    ///
+    /// let mut router = Router::new();
+    ///
+    /// router
+    ///   .get("/users/:id", |req, res| {
+    ///     res.status(StatusCode::Ok);
+    ///   })
+    ///   .name("user.show");
+    ///
+    /// assert_eq!(router.url_for("user.show", [("id", "42")]), Ok("/users/42".to_string()));
+    ///
+    /// assert_eq!(
+    ///   router.url_for("user.show", [("id", "john doe"), ("tab", "posts")]),
+    ///   Ok("/users/john%20doe?tab=posts".to_string())
+    /// );
+    ///
+    /// assert_eq!(
+    ///   router.url_for("user.show", []),
+    ///   Err(UrlError::MissingParameter { name: "user.show".to_string(), param: "id".to_string() })
+    /// );
+    /// ```
+    pub fn url_for<'a>(
+        &self,
+        name: &str,
+        params: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<String, UrlError> {
+        let path_types = self
+            .find_named(name)
+            .ok_or_else(|| UrlError::UnknownRoute(name.to_string()))?;
+        let params = params.into_iter().collect::<Vec<(&str, &str)>>();
+
+        let mut url = String::new();
+
+        for path_type in &path_types {
+            url.push('/');
+
+            match path_type {
+                PathType::Subdirectory(subdir) => url.push_str(subdir),
+                PathType::Parameter(param) => {
+                    let (_, value) =
+                        params.iter().find(|(key, _)| key == param).ok_or_else(|| {
+                            UrlError::MissingParameter {
+                                name: name.to_string(),
+                                param: param.clone(),
+                            }
+                        })?;
+
+                    url.push_str(&percent_encode(value));
+                }
+            }
+        }
+
+        if url.is_empty() {
+            url.push('/');
+        }
+
+        let query = params
+            .iter()
+            .filter(|(key, _)| !path_types.contains(&PathType::Parameter(key.to_string())))
+            .map(|(key, value)| format!("{}={}", percent_encode(key), percent_encode(value)))
+            .collect::<Vec<String>>();
+
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&query.join("&"));
+        }
+
+        Ok(url)
+    }
+
+    /// Returns the path of the endpoint with the name.
+    pub(super) fn find_named(&self, name: &str) -> Option<Vec<PathType>> {
+        if self
+            .endpoints
+            .iter()
+            .any(|endpoint| endpoint.get_name() == Some(name))
+        {
+            return Some(Vec::new());
+        }
+
+        let subdirs = self
+            .subdirs
+            .iter()
+            .map(|(subdir, router)| (PathType::Subdirectory(subdir.clone()), router));
+        let param_dirs = self
+            .param_dirs
+            .iter()
+            .map(|(param, router)| (PathType::Parameter(param.clone()), router));
+
+        let children = subdirs.chain(param_dirs).find_map(|(path_type, router)| {
+            let mut path = router.find_named(name)?;
+            path.insert(0, path_type);
+            Some(path)
+        });
+
+        children.or_else(|| {
+            self.mounted
+                .iter()
+                .find_map(|router| router.find_named(name))
+        })
+    }
+
+    /// Returns the names of every endpoint in the router tree.
+    pub(super) fn collect_names(&self) -> Vec<String> {
+        let endpoints = self
+            .endpoints
+            .iter()
+            .filter_map(|endpoint| endpoint.get_name().map(String::from));
+        let subdirs = self
+            .subdirs
+            .iter()
+            .flat_map(|(_, router)| router.collect_names());
+        let param_dirs = self
+            .param_dirs
+            .iter()
+            .flat_map(|(_, router)| router.collect_names());
+        let mounted = self
+            .mounted
+            .iter()
+            .flat_map(|router| router.collect_names());
+
+        endpoints
+            .chain(subdirs)
+            .chain(param_dirs)
+            .chain(mounted)
+            .collect()
+    }
+}
+
+/// Percent-encodes every byte except the unreserved characters of RFC 3986.
+pub(crate) fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Represents an error while building the URL of a named route
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlError {
+    /// There is no route with the name.
+    UnknownRoute(String),
+    /// A parameter of the route is not given.
+    MissingParameter {
+        /// Name of the route
+        name: String,
+        /// Name of the missing parameter
+        param: String,
+    },
+}
+
+impl Display for UrlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownRoute(name) => write!(f, "There is no route named {name}"),
+            Self::MissingParameter { name, param } => {
+                write!(f, "Parameter {param} is missing for route {name}")
+            }
+        }
+    }
+}
@@ -1,8 +1,12 @@
-use crate::{Request, Response, StatusCode};
-use std::fmt::{Debug, Formatter};
+use crate::{middleware::Next, BodyLimits, Request, Response, StatusCode};
+use std::{
+    fmt::{Debug, Formatter},
+    io::ErrorKind,
+    time::Duration,
+};
 
 use tokio::{
-    io::AsyncWriteExt,
+    io::{AsyncReadExt, AsyncWriteExt},
     net::{TcpListener, TcpStream},
 };
 
@@ -14,6 +18,7 @@ use route_handler::{HandlerResult, RouteHandler};
 pub struct Server {
     route_handlers: Vec<Box<dyn RouteHandler + Send>>,
     address: String,
+    body_limits: BodyLimits,
 }
 
 impl Server {
@@ -32,6 +37,7 @@ impl Server {
         Self {
             route_handlers: Vec::new(),
             address: String::from(""),
+            body_limits: BodyLimits::default(),
         }
     }
 
@@ -57,6 +63,22 @@ impl Server {
         self.route_handlers.push(Box::new(handler));
     }
 
+    /// Sets the size limits of the request bodies. Requests that exceed them get
+    /// `413 Payload Too Large`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use krustie::{ BodyLimits, Server };
+    ///
+    /// let mut server = Server::create();
+    ///
+    /// server.body_limits(BodyLimits::new().max_file_size(1024 * 1024));
+    /// ```
+    pub fn body_limits(&mut self, limits: BodyLimits) {
+        self.body_limits = limits;
+    }
+
     /// Listens for incoming requests on the specified IP and port
     ///
     /// # Example
@@ -68,7 +90,11 @@ impl Server {
     ///
     /// server.listen(8080);
     /// ```
-    pub async fn listen(self, port: u16) {
+    pub async fn listen(mut self, port: u16) {
+        for handler in &mut self.route_handlers {
+            handler.compile();
+        }
+
         Listener::listen(port, self).await;
     }
 
@@ -95,22 +121,21 @@ impl Server {
     /// ```
     pub async fn handle_stream(&mut self, stream: &mut TcpStream) {
         let mut response = Response::default();
-        let request_result = Request::parse(stream).await;
+        let request_result = Request::parse(stream, &self.body_limits).await;
+        let rejected = request_result.is_err();
 
         match request_result {
             Ok(request) => {
-                for handler in &mut self.route_handlers {
-                    let result = handler.handle(&request, &mut response);
-
-                    if result == HandlerResult::End {
-                        break;
-                    }
-                }
+                Self::run_handlers(&mut self.route_handlers, &request, &mut response);
             }
             Err(err) => {
-                response
-                    .status(StatusCode::BadRequest)
-                    .debug_msg(&err.to_string());
+                let status_code = match err.kind() {
+                    ErrorKind::FileTooLarge => StatusCode::PayloadTooLarge,
+                    ErrorKind::Unsupported => StatusCode::UnsupportedMediaType,
+                    _ => StatusCode::BadRequest,
+                };
+
+                response.status(status_code).debug_msg(&err.to_string());
             }
         }
         let response_stream: Vec<u8> = response.into();
@@ -121,6 +146,44 @@ impl Server {
                 eprintln!("error: {}", e);
             }
         }
+
+        if rejected {
+            Self::discard_input(stream).await;
+        }
+    }
+}
+
+impl Server {
+    /// Closes the writing side of a rejected request's stream and reads the rest of the request
+    /// for a while. Closing a stream with unread data resets the connection, and the client may
+    /// lose the response.
+    async fn discard_input(stream: &mut TcpStream) {
+        let mut buffer = [0; 8 * 1024];
+
+        let _ = stream.shutdown().await;
+        let _ = tokio::time::timeout(Duration::from_secs(1), async {
+            while let Ok(1..) = stream.read(&mut buffer).await {}
+        })
+        .await;
+    }
+
+    /// Runs the handlers in order. Every handler runs the ones after it through [Next], so
+    /// middlewares can run code after the rest of the handlers.
+    fn run_handlers(
+        handlers: &mut [Box<dyn RouteHandler + Send>],
+        request: &Request,
+        response: &mut Response,
+    ) -> HandlerResult {
+        match handlers.split_first_mut() {
+            Some((handler, rest)) => {
+                let mut continuation = |request: &Request, response: &mut Response| {
+                    Self::run_handlers(rest, request, response)
+                };
+
+                handler.handle_with_next(request, response, Next::new(&mut continuation))
+            }
+            None => HandlerResult::Next,
+        }
     }
 }
 
@@ -129,6 +192,7 @@ impl Clone for Server {
         Self {
             route_handlers: self.route_handlers.clone(),
             address: self.address.clone(),
+            body_limits: self.body_limits,
         }
     }
 }
@@ -1,6 +1,6 @@
 use dyn_clone::{clone_trait_object, DynClone};
 
-use crate::{Request, Response};
+use crate::{middleware::Next, Request, Response};
 
 /// Route handler trait
 ///
@@ -8,6 +8,26 @@ use crate::{Request, Response};
 pub trait RouteHandler: DynClone + Send {
     /// Handles the request and returns the result of the handler. It is used to define the handler for the routes and middlewares.
     fn handle(&mut self, request: &Request, response: &mut Response) -> HandlerResult;
+
+    /// Handles the request with the handlers that come after this one. By default, it runs
+    /// [RouteHandler::handle] and then the next handlers if it returns [HandlerResult::Next].
+    ///
+    /// Middlewares use [Middleware::around](crate::Middleware::around) here.
+    fn handle_with_next(
+        &mut self,
+        request: &Request,
+        response: &mut Response,
+        next: Next,
+    ) -> HandlerResult {
+        match self.handle(request, response) {
+            HandlerResult::End => HandlerResult::End,
+            HandlerResult::Next => next.run(request, response),
+        }
+    }
+
+    /// Prepares the handler once before the server starts to accept connections. Routers compile
+    /// their routes here. It does nothing by default.
+    fn compile(&mut self) {}
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -1,6 +1,6 @@
 use crate::{Request, Response};
 
-use super::{route_handler::HandlerResult, Server};
+use super::Server;
 
 impl Server {
     /// Mocks a request and returns a response
@@ -9,12 +9,7 @@ impl Server {
     pub fn mock_request(&mut self, request: Request) -> Response {
         let mut response = Response::default();
 
-        for handler in &mut self.route_handlers {
-            let result = handler.handle(&request, &mut response);
-            if result == HandlerResult::End {
-                break;
-            }
-        }
+        Self::run_handlers(&mut self.route_handlers, &request, &mut response);
 
         response
     }
@@ -25,12 +20,7 @@ impl Server {
     pub fn mock_request_and_expect(&mut self, request: Request, expected_response: Response) {
         let mut response = Response::default();
 
-        for handler in &mut self.route_handlers {
-            let result = handler.handle(&request, &mut response);
-            if result == HandlerResult::End {
-                break;
-            }
-        }
+        Self::run_handlers(&mut self.route_handlers, &request, &mut response);
 
         Response::assert_eq(&response, &expected_response);
     }
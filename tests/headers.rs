@@ -0,0 +1,237 @@
+use krustie::{
+    headers::{
+        Accept, AcceptEncoding, AcceptLanguage, Authorization, ByteRange, CacheControl,
+        CacheDirective, ContentRange, ETag, EntityTag, IfMatch, IfNoneMatch, QualityItem, Range,
+        Vary,
+    },
+    MediaType, Request, Response, StatusCode,
+    json::json,
+    response::ContentType,
+};
+
+#[test]
+fn quality_values() {
+    let accept = Accept::try_from("text/html;level=1;q=0.5, application/json, */*;Q=0").unwrap();
+    let ranges = accept.get_ranges();
+
+    assert_eq!(ranges.len(), 3);
+    assert_eq!(ranges[0].get_item().get_parameter("level"), Some("1"));
+    assert_eq!(ranges[0].get_quality(), 0.5);
+    assert_eq!(ranges[1].get_quality(), 1.0);
+    assert_eq!(ranges[2].get_item().essence(), "*/*");
+    assert_eq!(ranges[2].get_quality(), 0.0);
+
+    for invalid in [
+        "text/html;q=1.5",
+        "text/html;q=0.1234",
+        "text/html;q=x",
+        "html",
+    ] {
+        assert!(Accept::try_from(invalid).is_err(), "{invalid}");
+    }
+
+    let item = QualityItem::new(MediaType::try_from("text/csv").unwrap(), 0.25);
+    assert_eq!(item.to_string(), "text/csv;q=0.25");
+
+    let encoding = AcceptEncoding::try_from("GZIP, deflate;q=0.5, identity;q=0").unwrap();
+    let codings = encoding.get_codings();
+
+    assert_eq!(codings[0].get_item(), "gzip");
+    assert_eq!(encoding.to_string(), "gzip, deflate;q=0.5, identity;q=0");
+
+    let language = AcceptLanguage::try_from("en-US, tr;q=0.8, *;q=0.1").unwrap();
+    assert_eq!(language.get_languages()[1].get_item(), "tr");
+    assert!(AcceptLanguage::try_from("en_US").is_err());
+}
+
+#[test]
+fn authorization() {
+    let basic = Authorization::basic("jürgen", "p:ss");
+    let header = basic.to_string();
+
+    assert_eq!(Authorization::try_from(header.as_str()).unwrap(), basic);
+    assert_eq!(
+        Authorization::try_from("bearer abc.DEF-123==").unwrap(),
+        Authorization::bearer("abc.DEF-123==")
+    );
+    assert_eq!(
+        Authorization::try_from("Digest username=\"krustie\"").unwrap(),
+        Authorization::Other {
+            scheme: "Digest".to_string(),
+            credentials: "username=\"krustie\"".to_string(),
+        }
+    );
+
+    for invalid in ["Basic bm9jb2xvbg==", "Basic !!!!", "Bearer a b", "Bearer"] {
+        assert!(Authorization::try_from(invalid).is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn cache_control() {
+    let cache_control =
+        CacheControl::try_from("Max-Age=60, private=\"Set-Cookie\", community=\"UCI, x\"").unwrap();
+
+    assert_eq!(
+        cache_control.get_directives(),
+        [
+            CacheDirective::MaxAge(60),
+            CacheDirective::Private,
+            CacheDirective::Extension("community".to_string(), Some("UCI, x".to_string())),
+        ]
+    );
+    assert_eq!(
+        cache_control.to_string(),
+        "max-age=60, private, community=\"UCI, x\""
+    );
+    assert!(CacheControl::try_from("max-age=soon").is_err());
+    assert!(
+        CacheControl::try_from("max-stale")
+            .unwrap()
+            .contains(&CacheDirective::MaxStale(None))
+    );
+}
+
+#[test]
+fn entity_tags() {
+    let if_none_match = IfNoneMatch::try_from("\"a\", W/\"b\"").unwrap();
+
+    assert!(if_none_match.matches(&EntityTag::strong("b")));
+    assert!(!if_none_match.matches(&EntityTag::strong("c")));
+    assert_eq!(IfNoneMatch::try_from(" * ").unwrap(), IfNoneMatch::Any);
+    assert!(IfNoneMatch::try_from("a").is_err());
+
+    let if_match = IfMatch::try_from("W/\"a\", \"b\"").unwrap();
+
+    assert!(!if_match.matches(&EntityTag::strong("a")));
+    assert!(if_match.matches(&EntityTag::strong("b")));
+    assert_eq!(if_match.to_string(), "W/\"a\", \"b\"");
+    assert_eq!(ETag::new(EntityTag::weak("v1")).to_string(), "W/\"v1\"");
+}
+
+#[test]
+fn ranges() {
+    let range = Range::try_from("bytes=0-499, 9500-,-500").unwrap();
+
+    assert_eq!(range.get_ranges()[0].resolve(10_000), Some((0, 499)));
+    assert_eq!(range.get_ranges()[1].resolve(10_000), Some((9500, 9999)));
+    assert_eq!(range.get_ranges()[2].resolve(100), Some((0, 99)));
+    assert_eq!(ByteRange::From(100).resolve(100), None);
+    assert_eq!(range.to_string(), "bytes=0-499, 9500-, -500");
+
+    for invalid in ["bytes=", "bytes=5-1", "items=0-1", "bytes=a-b", "bytes=-"] {
+        assert!(Range::try_from(invalid).is_err(), "{invalid}");
+    }
+
+    assert_eq!(
+        ContentRange::try_from("bytes 0-99/*").unwrap(),
+        ContentRange::bytes(0, 99, None)
+    );
+    assert_eq!(
+        ContentRange::try_from("bytes */1000").unwrap(),
+        ContentRange::Unsatisfied(1000)
+    );
+}
+
+#[test]
+fn typed_headers_on_requests_and_responses() {
+    let request = Request::builder()
+        .headers(vec![
+            ("Accept", "text/html"),
+            ("Cache-Control", "max-age=nope"),
+        ])
+        .build();
+
+    let accept = request.typed_header::<Accept>().unwrap().unwrap();
+
+    assert_eq!(accept.get_ranges()[0].get_item().essence(), "text/html");
+    assert!(request.typed_header::<CacheControl>().is_err());
+    assert_eq!(request.typed_header::<Authorization>(), Ok(None));
+
+    let mut response = Response::default();
+
+    response
+        .typed_header(Vary::new().with("Accept").with("Accept-Encoding"))
+        .typed_header(ETag::new(EntityTag::strong("v1")));
+
+    assert_eq!(
+        response.get_header("vary"),
+        Some(&"Accept, Accept-Encoding".to_string())
+    );
+    assert_eq!(response.get_header("etag"), Some(&"\"v1\"".to_string()));
+}
+
+#[test]
+fn content_negotiation() {
+    let accept =
+        Accept::try_from("text/*;q=0.5, text/csv, application/json;q=0.5, */*;q=0").unwrap();
+    let quality = |media_type| accept.get_quality(&MediaType::try_from(media_type).unwrap());
+
+    assert_eq!(quality("text/csv"), 1.0);
+    assert_eq!(quality("text/html"), 0.5);
+    assert_eq!(quality("image/png"), 0.0);
+
+    // The first of the equally specific ranges is used, whatever their qualities are
+    for (header, expected) in [
+        ("text/*;q=0.5, text/*;q=0.9", 0.5),
+        ("text/*;q=0.9, text/*;q=0.5", 0.9),
+    ] {
+        let accept = Accept::try_from(header).unwrap();
+        let media_type = MediaType::try_from("text/html").unwrap();
+
+        assert_eq!(accept.get_quality(&media_type), expected, "{header}");
+    }
+
+    let available = [ContentType::Json, ContentType::Html, ContentType::Csv];
+
+    assert_eq!(accept.negotiate(&available), Some(&ContentType::Csv));
+    assert_eq!(accept.negotiate(&available[..2]), Some(&ContentType::Json));
+    assert_eq!(accept.negotiate(&[ContentType::Png]), None);
+
+    let request = Request::builder().build();
+    assert_eq!(request.negotiate(&available), Some(&ContentType::Json));
+
+    let render = |accept: &str| {
+        let request = Request::builder().headers(vec![("Accept", accept)]).build();
+        let mut response = Response::default();
+
+        response
+            .status(StatusCode::Ok)
+            .set_header("Vary", "Accept-Encoding")
+            .format(
+                &request,
+                &[
+                    (ContentType::Json, &|res| {
+                        res.body_json(json!({ "name": "John" }));
+                    }),
+                    (ContentType::Csv, &|res| {
+                        res.body_text("name\nJohn\n");
+                    }),
+                ],
+            );
+
+        response
+    };
+
+    let csv = render("text/csv, application/json;q=0.9");
+
+    assert_eq!(csv.get_status(), StatusCode::Ok);
+    assert_eq!(
+        csv.get_header("content-type"),
+        Some(&"text/csv".to_string())
+    );
+    assert_eq!(csv.get_body(), b"name\nJohn\n");
+    assert_eq!(
+        csv.get_header("vary"),
+        Some(&"Accept-Encoding, Accept".to_string())
+    );
+
+    let not_acceptable = render("text/html");
+
+    assert_eq!(not_acceptable.get_status(), StatusCode::NotAcceptable);
+    assert!(not_acceptable.get_body().is_empty());
+    assert_eq!(
+        not_acceptable.get_header("vary"),
+        Some(&"Accept-Encoding, Accept".to_string())
+    );
+}
@@ -0,0 +1,494 @@
+use krustie::{
+    middlewares::GzipEncoder, Endpoint, HandlerResult, HttpMethod, Middleware, Next, Request,
+    Response, Router, RouterError, Server, StatusCode,
+};
+
+#[derive(Clone)]
+struct AppendHeader(&'static str);
+
+impl Middleware for AppendHeader {
+    fn middleware(&mut self, _: &Request, response: &mut Response) -> HandlerResult {
+        let value = match response.get_header("X-Trace") {
+            Some(value) => format!("{value},{}", self.0),
+            None => self.0.to_string(),
+        };
+
+        response.set_header("X-Trace", &value);
+        HandlerResult::Next
+    }
+}
+
+#[derive(Clone)]
+struct Deny;
+
+impl Middleware for Deny {
+    fn middleware(&mut self, _: &Request, response: &mut Response) -> HandlerResult {
+        response.status(StatusCode::Unauthorized);
+        HandlerResult::End
+    }
+}
+
+/// Appends its name to `X-Trace` before and after the next handlers.
+#[derive(Clone)]
+struct Around(&'static str);
+
+impl Middleware for Around {
+    fn around(&mut self, request: &Request, response: &mut Response, next: Next) -> HandlerResult {
+        AppendHeader(self.0).middleware(request, response);
+        let result = next.run(request, response);
+        AppendHeader(self.0).middleware(request, response);
+
+        result
+    }
+}
+
+fn ok(_: &Request, response: &mut Response) {
+    response.status(StatusCode::Ok);
+}
+
+fn trace(server: &mut Server, path: &str) -> (StatusCode, Option<String>) {
+    let request = Request::builder().path(path).build();
+    let response = server.mock_request(request);
+
+    (
+        response.get_status(),
+        response.get_header("X-Trace").cloned(),
+    )
+}
+
+#[test]
+fn router_middlewares_run_in_order() {
+    let mut router = Router::new();
+
+    router
+        .use_middleware(AppendHeader("first"))
+        .use_middleware(AppendHeader("second"))
+        .get("/", ok);
+
+    let mut server = Server::create();
+    server.use_handler(router);
+
+    assert_eq!(
+        trace(&mut server, "/"),
+        (StatusCode::Ok, Some("first,second".to_string()))
+    );
+}
+
+#[test]
+fn router_middlewares_are_scoped_to_subtree() {
+    let mut main_router = Router::new();
+    let mut admin_router = Router::new();
+
+    admin_router
+        .use_middleware(AppendHeader("admin"))
+        .get("/stats", ok);
+
+    main_router
+        .use_middleware(AppendHeader("main"))
+        .use_middleware_at("/public", AppendHeader("public"))
+        .get("/public/info", ok)
+        .get("/about", ok)
+        .use_router("/admin", admin_router);
+
+    let mut server = Server::create();
+    server.use_handler(main_router);
+
+    assert_eq!(
+        trace(&mut server, "/admin/stats"),
+        (StatusCode::Ok, Some("main,admin".to_string()))
+    );
+    assert_eq!(
+        trace(&mut server, "/public/info"),
+        (StatusCode::Ok, Some("main,public".to_string()))
+    );
+    assert_eq!(
+        trace(&mut server, "/about"),
+        (StatusCode::Ok, Some("main".to_string()))
+    );
+}
+
+#[test]
+fn merged_router_middlewares_are_scoped_to_their_routes() {
+    let mut main_router = Router::new();
+    let mut users_router = Router::new();
+    let mut admin_router = Router::new();
+
+    users_router
+        .use_middleware(AppendHeader("users"))
+        .get("/users", ok);
+    admin_router
+        .use_middleware(AppendHeader("admin"))
+        .get("/admin", ok)
+        .get("/users/:id", ok);
+
+    main_router
+        .use_middleware(AppendHeader("main"))
+        .use_router("/", users_router);
+    main_router.use_router("/", admin_router);
+    main_router.get("/health", ok);
+
+    let mut server = Server::create();
+    server.use_handler(main_router);
+
+    assert_eq!(
+        trace(&mut server, "/users"),
+        (StatusCode::Ok, Some("main,users".to_string()))
+    );
+    assert_eq!(
+        trace(&mut server, "/users/1"),
+        (StatusCode::Ok, Some("main,admin".to_string()))
+    );
+    assert_eq!(
+        trace(&mut server, "/admin"),
+        (StatusCode::Ok, Some("main,admin".to_string()))
+    );
+    assert_eq!(
+        trace(&mut server, "/health"),
+        (StatusCode::Ok, Some("main".to_string()))
+    );
+}
+
+#[test]
+fn path_middlewares_run_for_routers_mounted_at_root() {
+    let mut users_router = Router::new();
+    let mut admin_router = Router::new();
+
+    users_router
+        .use_middleware(AppendHeader("users"))
+        .get("/users", ok);
+    admin_router
+        .use_middleware(AppendHeader("admin"))
+        .get("/admin/:id", ok);
+
+    let mut main_router = Router::new();
+
+    main_router
+        .use_middleware_at("/users", AppendHeader("at"))
+        .use_router("/", users_router);
+    main_router.use_router("/", admin_router);
+    main_router.use_middleware_at("/admin/:key", AppendHeader("key"));
+
+    let mut server = Server::create();
+    server.use_handler(main_router);
+
+    assert_eq!(
+        trace(&mut server, "/users"),
+        (StatusCode::Ok, Some("at,users".to_string()))
+    );
+    assert_eq!(
+        trace(&mut server, "/admin/1"),
+        (StatusCode::Ok, Some("key,admin".to_string()))
+    );
+}
+
+#[test]
+fn path_middlewares_run_for_routers_mounted_at_path() {
+    let mut users_router = Router::new();
+
+    users_router
+        .use_middleware(AppendHeader("users"))
+        .get("/users", ok)
+        .get("/posts", ok);
+
+    let mut main_router = Router::new();
+
+    main_router
+        .use_middleware_at("/api", AppendHeader("api"))
+        .use_router("/api", users_router);
+    main_router.use_middleware_at("/api/users", AppendHeader("at"));
+
+    let routes = main_router.routes();
+    let middlewares = routes
+        .get_routes()
+        .iter()
+        .map(|route| route.get_middlewares().len())
+        .collect::<Vec<usize>>();
+
+    assert_eq!(middlewares, [2, 3]);
+
+    let mut server = Server::create();
+    server.use_handler(main_router);
+
+    assert_eq!(
+        trace(&mut server, "/api/users"),
+        (StatusCode::Ok, Some("api,at,users".to_string()))
+    );
+    assert_eq!(
+        trace(&mut server, "/api/posts"),
+        (StatusCode::Ok, Some("api,users".to_string()))
+    );
+}
+
+#[test]
+fn path_middlewares_match_parameters_by_position() {
+    let mut uid_router = Router::new();
+
+    uid_router
+        .use_middleware(AppendHeader("uid"))
+        .get("/settings", ok);
+
+    let mut main_router = Router::new();
+
+    main_router
+        .use_middleware_at("/users/:id", AppendHeader("id"))
+        .use_middleware_at("/users/:id/posts", AppendHeader("posts"))
+        .get("/users/:user_id/posts", ok)
+        .get("/users/:name", ok)
+        .get("/users", ok)
+        .use_router("/users/:uid", uid_router);
+
+    let routes = main_router.routes();
+    let middlewares = routes
+        .get_routes()
+        .iter()
+        .map(|route| (route.get_path(), route.get_middlewares().len()))
+        .collect::<Vec<(&str, usize)>>();
+
+    assert_eq!(
+        middlewares,
+        [
+            ("/users", 0),
+            ("/users/:name", 1),
+            ("/users/:uid/settings", 2),
+            ("/users/:user_id/posts", 2)
+        ]
+    );
+
+    let mut server = Server::create();
+    server.use_handler(main_router);
+
+    assert_eq!(
+        trace(&mut server, "/users/1/posts"),
+        (StatusCode::Ok, Some("id,posts".to_string()))
+    );
+    assert_eq!(
+        trace(&mut server, "/users/1"),
+        (StatusCode::Ok, Some("id".to_string()))
+    );
+    assert_eq!(
+        trace(&mut server, "/users/1/settings"),
+        (StatusCode::Ok, Some("id,uid".to_string()))
+    );
+    assert_eq!(trace(&mut server, "/users"), (StatusCode::Ok, None));
+}
+
+#[test]
+fn path_middlewares_match_parameters_of_routers_mounted_at_root() {
+    let mut users_router = Router::new();
+
+    users_router
+        .use_middleware(AppendHeader("users"))
+        .get("/users/:uid", ok);
+
+    let mut main_router = Router::new();
+
+    main_router
+        .use_middleware_at("/users/:id", AppendHeader("id"))
+        .use_router("/", users_router);
+
+    let mut server = Server::create();
+    server.use_handler(main_router);
+
+    assert_eq!(
+        trace(&mut server, "/users/1"),
+        (StatusCode::Ok, Some("id,users".to_string()))
+    );
+}
+
+#[test]
+fn invalid_middleware_path() {
+    let mut router = Router::new();
+
+    assert_eq!(
+        router.try_use_middleware_at("/admin/:", AppendHeader("admin")),
+        Err(RouterError::InvalidSegment(":".to_string()))
+    );
+}
+
+#[test]
+fn middleware_ends_execution() {
+    let mut router = Router::new();
+
+    router
+        .use_middleware_at("/admin", Deny)
+        .get("/admin", ok)
+        .get("/", ok);
+
+    let mut server = Server::create();
+    server.use_handler(router);
+
+    assert_eq!(trace(&mut server, "/admin").0, StatusCode::Unauthorized);
+    assert_eq!(trace(&mut server, "/").0, StatusCode::Ok);
+}
+
+#[test]
+fn endpoint_middlewares_run_before_controller() {
+    let mut router = Router::new();
+
+    let endpoint = Endpoint::new_with_middleware(
+        HttpMethod::GET,
+        ok,
+        vec![
+            Box::new(AppendHeader("endpoint")),
+            Box::new(AppendHeader("again")),
+        ],
+    );
+
+    router.use_middleware(AppendHeader("router"));
+    router.use_endpoint("/", endpoint);
+
+    let mut server = Server::create();
+    server.use_handler(router);
+
+    assert_eq!(
+        trace(&mut server, "/"),
+        (StatusCode::Ok, Some("router,endpoint,again".to_string()))
+    );
+}
+
+#[test]
+fn onion_middlewares_at_every_level() {
+    let mut router = Router::new();
+
+    let endpoint = Endpoint::new_with_middleware(
+        HttpMethod::GET,
+        |_, res| {
+            AppendHeader("controller").middleware(&Request::default(), res);
+            res.status(StatusCode::Ok);
+        },
+        vec![Box::new(Around("endpoint"))],
+    );
+
+    router.use_middleware(Around("router"));
+    router.use_endpoint("/", endpoint);
+
+    let mut server = Server::create();
+    server.use_handler(Around("server"));
+    server.use_handler(router);
+
+    assert_eq!(
+        trace(&mut server, "/"),
+        (
+            StatusCode::Ok,
+            Some("server,router,endpoint,controller,endpoint,router,server".to_string())
+        )
+    );
+}
+
+#[test]
+fn onion_middleware_sees_ended_execution() {
+    let mut router = Router::new();
+
+    router.use_middleware(Deny).get("/", ok);
+
+    let mut server = Server::create();
+    server.use_handler(Around("server"));
+    server.use_handler(router);
+    server.use_handler(AppendHeader("unreachable"));
+
+    assert_eq!(
+        trace(&mut server, "/"),
+        (StatusCode::Unauthorized, Some("server,server".to_string()))
+    );
+}
+
+#[test]
+fn gzip_encoder_before_router() {
+    let mut router = Router::new();
+
+    router.get("/", |_, res| {
+        res.status(StatusCode::Ok).body_text("Hello, World!");
+    });
+
+    let mut server = Server::create();
+    server.use_handler(GzipEncoder);
+    server.use_handler(router);
+
+    let request = Request::builder()
+        .path("/")
+        .header("accept-encoding", "gzip, deflate")
+        .build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::Ok);
+    assert_eq!(
+        response.get_header("Content-Encoding"),
+        Some(&"gzip".to_string())
+    );
+    assert_eq!(&response.get_body()[..2], &[0x1f, 0x8b]);
+}
+
+#[test]
+fn gzip_encoder_quality_values() {
+    let mut router = Router::new();
+
+    router.get("/", |_, res| {
+        res.status(StatusCode::Ok).body_text("Hello, World!");
+    });
+
+    let mut server = Server::create();
+    server.use_handler(GzipEncoder);
+    server.use_handler(router);
+
+    for (accept_encoding, compressed) in [
+        ("deflate, gzip;q=0.8", true),
+        ("deflate, *;q=0.5", true),
+        ("gzip;q=0, *", false),
+        ("br", false),
+    ] {
+        let request = Request::builder()
+            .path("/")
+            .header("Accept-Encoding", accept_encoding)
+            .build();
+        let response = server.mock_request(request);
+
+        assert_eq!(
+            response.get_header("Content-Encoding").is_some(),
+            compressed,
+            "{accept_encoding}"
+        );
+    }
+}
+
+#[test]
+fn gzip_encoder_varies_on_accept_encoding() {
+    let mut router = Router::new();
+
+    router
+        .get("/", |_, res| {
+            res.status(StatusCode::Ok).body_text("Hello, World!");
+        })
+        .get("/accept", |_, res| {
+            res.status(StatusCode::Ok)
+                .set_header("Vary", "Accept")
+                .body_text("Hello, World!");
+        })
+        .get("/empty", ok);
+
+    let mut server = Server::create();
+    server.use_handler(GzipEncoder);
+    server.use_handler(router);
+
+    for (path, accept_encoding, vary) in [
+        ("/", Some("gzip"), Some("Accept-Encoding")),
+        ("/", Some("br"), Some("Accept-Encoding")),
+        ("/", None, Some("Accept-Encoding")),
+        ("/accept", Some("gzip"), Some("Accept, Accept-Encoding")),
+        ("/empty", Some("gzip"), None),
+    ] {
+        let mut request = Request::builder();
+        request.path(path);
+
+        if let Some(accept_encoding) = accept_encoding {
+            request.header("Accept-Encoding", accept_encoding);
+        }
+
+        let response = server.mock_request(request.build());
+
+        assert_eq!(
+            response.get_header("Vary").map(String::as_str),
+            vary,
+            "{path} {accept_encoding:?}"
+        );
+    }
+}
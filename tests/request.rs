@@ -0,0 +1,728 @@
+use krustie::{
+    json::json, BodyLimits, ExtractSource, HttpMethod, MediaType, Request, RequestBody, Response,
+    Router, Server, StatusCode,
+};
+use serde::Deserialize;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+#[derive(Deserialize)]
+struct UserPath {
+    id: u32,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Pagination {
+    page: u32,
+    limit: Option<u32>,
+    desc: bool,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Login {
+    username: String,
+    remember_me: bool,
+    #[serde(default)]
+    role: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Tags {
+    tag: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+struct ApiKey {
+    x_api_key: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Address {
+    city: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct NewUser {
+    name: String,
+    address: Address,
+}
+
+#[test]
+fn typed_params() {
+    let mut server = Server::create();
+    let mut router = Router::new();
+
+    router.get("/users/:id", |req, res| match req.params::<UserPath>() {
+        Ok(path) => {
+            res.status(StatusCode::Ok)
+                .body_text(&(path.id + 1).to_string());
+        }
+        Err(err) => err.respond(res),
+    });
+
+    server.use_handler(router);
+
+    let request = Request::builder().path("/users/41").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::Ok);
+    assert_eq!(response.get_body(), b"42");
+
+    let request = Request::builder().path("/users/john").build();
+    let response = server.mock_request(request);
+
+    let mut expected_response = Response::default();
+    expected_response
+        .status(StatusCode::BadRequest)
+        .body_json(json!({
+            "error": "Bad Request",
+            "source": "params",
+            "field": "id",
+            "reason": "invalid value: string \"john\", expected u32",
+        }));
+
+    Response::assert_eq(&expected_response, &response);
+}
+
+#[test]
+fn typed_query() {
+    let request = Request::builder().path("/users?page=2&desc=true").build();
+
+    assert_eq!(
+        request.query::<Pagination>().unwrap(),
+        Pagination {
+            page: 2,
+            limit: None,
+            desc: true,
+        }
+    );
+
+    let request = Request::builder().path("/users?desc=false").build();
+    let err = request.query::<Pagination>().unwrap_err();
+
+    assert_eq!(err.get_source(), ExtractSource::Query);
+    assert_eq!(err.get_field(), Some("page"));
+    assert_eq!(err.get_reason(), "missing field");
+    assert_eq!(err.get_status(), StatusCode::BadRequest);
+}
+
+#[test]
+fn typed_headers() {
+    let request = Request::builder().header("X-Api-Key", "secret").build();
+
+    assert_eq!(
+        request.headers::<ApiKey>().unwrap(),
+        ApiKey {
+            x_api_key: "secret".to_string()
+        }
+    );
+
+    let request = Request::builder().build();
+    let err = request.headers::<ApiKey>().unwrap_err();
+
+    assert_eq!(err.get_field(), Some("x-api-key"));
+    assert_eq!(
+        err.to_string(),
+        "Invalid headers field `x-api-key`: missing field"
+    );
+}
+
+#[test]
+fn typed_json() {
+    let request = Request::builder()
+        .method(HttpMethod::POST)
+        .body(RequestBody::Json(
+            json!({ "name": "John", "address": { "city": "Istanbul" } }),
+        ))
+        .build();
+
+    assert_eq!(
+        request.json::<NewUser>().unwrap(),
+        NewUser {
+            name: "John".to_string(),
+            address: Address {
+                city: "Istanbul".to_string()
+            },
+        }
+    );
+
+    let request = Request::builder()
+        .method(HttpMethod::POST)
+        .body(RequestBody::Json(
+            json!({ "name": "John", "address": { "city": 42 } }),
+        ))
+        .build();
+    let err = request.json::<NewUser>().unwrap_err();
+
+    assert_eq!(err.get_field(), Some("address.city"));
+    assert_eq!(err.get_status(), StatusCode::UnprocessableEntity);
+
+    let request = Request::builder()
+        .method(HttpMethod::POST)
+        .body(RequestBody::Json(json!({ "name": "John", "address": {} })))
+        .build();
+    let err = request.json::<NewUser>().unwrap_err();
+
+    assert_eq!(err.get_field(), Some("address.city"));
+    assert_eq!(err.get_reason(), "missing field");
+
+    let request = Request::builder()
+        .method(HttpMethod::POST)
+        .body(RequestBody::Text("John".to_string()))
+        .build();
+    let err = request.json::<NewUser>().unwrap_err();
+
+    assert_eq!(err.get_source(), ExtractSource::Body);
+    assert_eq!(err.get_field(), None);
+    assert_eq!(err.get_status(), StatusCode::BadRequest);
+}
+
+#[test]
+fn percent_decoding() {
+    let mut server = Server::create();
+    let mut router = Router::new();
+
+    router.get("/cities/:name", |req, res| {
+        let body = format!(
+            "{}|{}|{:?}|{:?}",
+            req.get_param("name").unwrap(),
+            req.get_path_array().join("/"),
+            req.get_query_all("tag"),
+            req.get_query_param("debug"),
+        );
+
+        res.status(StatusCode::Ok).body_text(&body);
+    });
+
+    server.use_handler(router);
+
+    let request = Request::builder()
+        .path("/cities/New%20York?tag=big+apple&debug&tag=caf%C3%A9")
+        .build();
+    let response = server.mock_request(request);
+
+    assert_eq!(
+        String::from_utf8_lossy(response.get_body()),
+        r#"New York|cities/New York|["big apple", "café"]|Some("")"#
+    );
+}
+
+#[test]
+fn percent_encoded_routes() {
+    let mut server = Server::create();
+    let mut router = Router::new();
+
+    router
+        .get("/caf", |_, res| {
+            res.status(StatusCode::Ok).body_text("caf");
+        })
+        .get("/files/:name", |req, res| {
+            res.status(StatusCode::Ok)
+                .body_text(req.get_param("name").unwrap());
+        });
+
+    server.use_handler(router);
+
+    let mut send = |path: &str| {
+        let response = server.mock_request(Request::builder().path(path).build());
+
+        (
+            response.get_status(),
+            String::from_utf8_lossy(response.get_body()).to_string(),
+        )
+    };
+
+    assert_eq!(send("/%63af"), (StatusCode::Ok, "caf".to_string()));
+    assert_eq!(send("/%66iles/%2541"), (StatusCode::Ok, "%41".to_string()));
+    // An encoded slash is a part of the segment and does not separate the segments
+    assert_eq!(send("/files/a%2Fb"), (StatusCode::Ok, "a/b".to_string()));
+    assert_eq!(send("/files%2Fa").0, StatusCode::NotFound);
+}
+
+#[test]
+fn typed_query_with_repeated_keys() {
+    let request = Request::builder().path("/posts?tag=rust&tag=web").build();
+
+    assert_eq!(
+        request.query::<Tags>().unwrap(),
+        Tags {
+            tag: vec!["rust".to_string(), "web".to_string()]
+        }
+    );
+}
+
+#[tokio::test]
+async fn invalid_percent_encoding() {
+    for target in ["/search?q=%ZZ", "/search?q=%FF", "/files/%E2%28"] {
+        let request = format!("GET {target} HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        let response = send(Server::create(), request.as_bytes()).await;
+
+        assert!(response.starts_with("HTTP/1.1 400"), "{target}: {response}");
+    }
+}
+
+#[test]
+fn request_target_forms() {
+    let request = Request::builder()
+        .path("/hello/world?city=istanbul#top")
+        .build();
+
+    assert_eq!(request.get_path(), "/hello/world");
+    assert_eq!(request.get_path_array(), &["hello", "world"]);
+    assert_eq!(request.get_raw_query(), Some("city=istanbul"));
+    assert_eq!(request.get_fragment(), Some("top"));
+
+    let request = Request::builder()
+        .path("http://example.com:8080/hello?city=istanbul")
+        .build();
+
+    assert_eq!(request.get_path(), "/hello");
+    assert_eq!(request.get_query_param("city").unwrap(), "istanbul");
+
+    let request = Request::builder().path("http://example.com").build();
+
+    assert_eq!(request.get_path(), "/");
+    assert_eq!(request.get_raw_query(), None);
+
+    let request = Request::builder().path("*").build();
+
+    assert_eq!(request.get_path(), "*");
+    assert!(request.get_path_array().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Invalid request target: hello/world")]
+fn invalid_request_target() {
+    Request::builder().path("hello/world");
+}
+
+#[test]
+#[should_panic(expected = "Invalid percent-encoding")]
+fn invalid_request_target_encoding() {
+    Request::builder().path("/users/%zz");
+}
+
+#[test]
+#[should_panic(expected = "Invalid percent-encoding: %zz")]
+fn invalid_query_encoding() {
+    Request::builder().path("/x?a=%zz&b=1");
+}
+
+#[tokio::test]
+async fn media_types() {
+    let mut server = Server::create();
+    let mut router = Router::new();
+
+    router.post("/echo", |req, res| {
+        let body = match req.get_body() {
+            RequestBody::Json(json) => format!("json {json}"),
+            RequestBody::Text(text) => format!("text {text}"),
+            RequestBody::Binary(bytes) => format!("binary {}", bytes.len()),
+            _ => "other".to_string(),
+        };
+
+        res.status(StatusCode::Ok).body_text(&body);
+    });
+
+    server.use_handler(router);
+
+    let cases: [(&str, &[u8], &str); 5] = [
+        (
+            "application/json; charset=utf-8",
+            br#"{"id":1}"#,
+            r#"json {"id":1}"#,
+        ),
+        ("Application/Vnd.Api+JSON", br#"[true]"#, "json [true]"),
+        (
+            "text/plain; charset=iso-8859-1",
+            b"J\xfcrgen",
+            "text Jürgen",
+        ),
+        ("text/csv", b"id,name", "text id,name"),
+        ("image/png", b"\x89PNG", "binary 4"),
+    ];
+
+    for (content_type, body, expected) in cases {
+        let response = send(server.clone(), &post("/echo", content_type, body)).await;
+
+        assert!(response.ends_with(expected), "{content_type}: {response}");
+    }
+
+    let response = send(server.clone(), &post("/echo", "text/plain", b"\xff\xfe")).await;
+    assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+
+    let response = send(
+        server.clone(),
+        &post("/echo", "text/plain; charset=koi8-r", b"x"),
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 415"), "{response}");
+
+    let response = send(server.clone(), &post("/echo", "json", b"{}")).await;
+    assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+
+    let response = send(server, &post("/echo", "json", b"")).await;
+    assert!(response.ends_with("other"), "{response}");
+}
+
+#[test]
+fn media_type_parameters() {
+    let media_type =
+        MediaType::try_from("multipart/form-data; boundary=\"a;b\"; charset=utf-8").unwrap();
+
+    assert_eq!(media_type.get_parameter("boundary"), Some("a;b"));
+    assert_eq!(media_type.get_parameter("charset"), Some("utf-8"));
+
+    let media_type =
+        MediaType::try_from("text/plain; format; =flowed; charset=\"utf-8\"; delsp=yes").unwrap();
+
+    assert_eq!(
+        media_type.get_parameters(),
+        [
+            ("charset".to_string(), "utf-8".to_string()),
+            ("delsp".to_string(), "yes".to_string())
+        ]
+    );
+}
+
+#[tokio::test]
+async fn multipart_disposition_parameters() {
+    let mut server = Server::create();
+    let mut router = Router::new();
+
+    router.post("/upload", |req, res| {
+        let RequestBody::Multipart(multipart) = req.get_body() else {
+            res.status(StatusCode::BadRequest);
+            return;
+        };
+
+        let file = multipart.get_file("a;b").unwrap();
+        let note = multipart.get_field("note").unwrap();
+
+        res.status(StatusCode::Ok)
+            .body_text(&format!("{}|{note}", file.get_file_name()));
+    });
+
+    server.use_handler(router);
+
+    let body = multipart_body(&[
+        ("inline; name=\"a;b\"; filename=\"x;y.txt\"", None, b"x"),
+        ("name=note; broken", None, b"hello"),
+    ]);
+    let response = send(server, &post("/upload", MULTIPART, &body)).await;
+
+    assert!(response.ends_with("x;y.txt|hello"), "{response}");
+}
+
+#[tokio::test]
+async fn non_utf8_input() {
+    let mut server = Server::create();
+    let mut router = Router::new();
+
+    router.post("/echo", |req, res| {
+        let agent = req.get_header("user-agent").unwrap_or_default();
+        let body = match req.get_body() {
+            RequestBody::Text(text) => text.as_str(),
+            _ => "",
+        };
+
+        res.status(StatusCode::Ok)
+            .body_text(&format!("{agent}|{body}"));
+    });
+
+    server.use_handler(router);
+
+    let body = "Grüße, 世界".as_bytes();
+    let mut request = post("/echo", "text/plain; charset=utf-8", body);
+    let user_agent = b"User-Agent: caf\xe9\r\n";
+    let index = request
+        .windows(2)
+        .position(|window| window == b"\r\n")
+        .unwrap()
+        + 2;
+
+    request.splice(index..index, user_agent.iter().copied());
+
+    let response = send(server.clone(), &request).await;
+    assert!(response.ends_with("café|Grüße, 世界"), "{response}");
+
+    let malformed: [&[u8]; 4] = [
+        b"GET /\xff HTTP/1.1\r\n\r\n",
+        b"GET / HTTP/1.1\r\nNo colon\r\n\r\n",
+        b"GET / HTTP/1.1\r\nBad Name: value\r\n\r\n",
+        b"GET / HTTP/1.1\r\nContent-Length: ten\r\n\r\n",
+    ];
+
+    for request in malformed {
+        let response = send(server.clone(), request).await;
+
+        assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+    }
+
+    // The headers after the 100th are not dropped, the request is rejected
+    let headers = (0..101)
+        .map(|index| format!("X-Header-{index}: {index}\r\n"))
+        .collect::<String>();
+    let request = format!("POST /echo HTTP/1.1\r\n{headers}\r\n");
+
+    let response = send(server.clone(), request.as_bytes()).await;
+    assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+
+    // A body in ASCII can not have non-ASCII bytes
+    let response = send(
+        server.clone(),
+        &post("/echo", "text/plain; charset=us-ascii", b"caf\xe9"),
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+
+    let response = send(server, &post("/echo", "text/plain; charset=ascii", b"cafe")).await;
+    assert!(response.ends_with("|cafe"), "{response}");
+}
+
+#[tokio::test]
+async fn repeated_headers() {
+    let mut server = Server::create();
+    let mut router = Router::new();
+
+    router.get("/", |req, res| {
+        let headers = req.get_headers();
+        let body = format!(
+            "{}|{}|{}",
+            req.get_header("HOST").unwrap_or_default(),
+            req.get_header("referer").unwrap_or_default(),
+            headers
+                .get_all("accept")
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        res.status(StatusCode::Ok)
+            .append_header("Set-Cookie", "theme=dark")
+            .append_header("set-cookie", "lang=en")
+            .body_text(&body);
+    });
+
+    server.use_handler(router);
+
+    let request = b"GET / HTTP/1.1\r\nHost: localhost:8080\r\nReferer: http://localhost:8080/a\r\nAccept: text/html\r\naccept: application/json\r\n\r\n";
+    let response = send(server, request).await;
+
+    assert!(
+        response.ends_with("localhost:8080|http://localhost:8080/a|text/html, application/json"),
+        "{response}"
+    );
+    assert!(
+        response.contains("Set-Cookie: theme=dark\r\nset-cookie: lang=en\r\n"),
+        "{response}"
+    );
+
+    let request = Request::builder()
+        .header("Content-Type", "application/json")
+        .header("content-type", "text/plain")
+        .build();
+
+    assert_eq!(request.get_header("CONTENT-TYPE"), Some("text/plain"));
+    assert_eq!(request.get_headers().len(), 1);
+}
+
+#[tokio::test]
+async fn form_body() {
+    let mut server = Server::create();
+    let mut router = Router::new();
+
+    router.post("/login", |req, res| match req.form::<Login>() {
+        Ok(login) => {
+            let body = format!("{}|{}|{:?}", login.username, login.remember_me, login.role);
+            res.status(StatusCode::Ok).body_text(&body);
+        }
+        Err(err) => err.respond(res),
+    });
+
+    server.use_handler(router);
+
+    let form = "application/x-www-form-urlencoded";
+
+    let body = b"username=J%C3%BCrgen+M&remember_me=true&role=admin&role=dev";
+    let response = send(server.clone(), &post("/login", form, body)).await;
+
+    assert!(
+        response.ends_with(r#"Jürgen M|true|["admin", "dev"]"#),
+        "{response}"
+    );
+
+    let latin1 = format!("{form}; charset=ISO-8859-1");
+    let body = b"username=J%FCrgen&remember_me=false";
+    let response = send(server.clone(), &post("/login", &latin1, body)).await;
+
+    assert!(response.ends_with("Jürgen|false|[]"), "{response}");
+
+    let body = b"remember_me=maybe";
+    let response = send(server.clone(), &post("/login", form, body)).await;
+
+    assert!(response.starts_with("HTTP/1.1 422"), "{response}");
+    assert!(response.contains(r#""field":"remember_me""#), "{response}");
+
+    let body = b"username=%E2%28";
+    let response = send(server, &post("/login", form, body)).await;
+
+    assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+}
+
+#[tokio::test]
+async fn multipart_upload() {
+    let mut server = Server::create();
+    let mut router = Router::new();
+
+    router.post("/upload", |req, res| {
+        let RequestBody::Multipart(multipart) = req.get_body() else {
+            res.status(StatusCode::BadRequest);
+            return;
+        };
+
+        let login = req.form::<Login>().unwrap();
+        let avatar = multipart.get_file("avatar").unwrap();
+        let content = avatar.read().unwrap();
+        let notes = multipart.get_file("notes").unwrap();
+
+        let body = format!(
+            "{}|{:?}|{}|{:?}|{}|{}|{}|{}",
+            login.username,
+            login.role,
+            avatar.get_file_name(),
+            avatar.get_content_type(),
+            avatar.get_size(),
+            content.iter().all(|&byte| byte == b'a'),
+            notes.get_file_name(),
+            String::from_utf8(notes.read().unwrap()).unwrap(),
+        );
+
+        res.status(StatusCode::Ok).body_text(&body);
+    });
+
+    server.use_handler(router);
+
+    let avatar = vec![b'a'; 100 * 1024];
+    let body = multipart_body(&[
+        ("name=\"username\"", None, b"J\xc3\xbcrgen"),
+        ("name=\"remember_me\"", None, b"true"),
+        ("name=\"role\"", None, b"admin"),
+        ("name=\"role\"", None, b"dev"),
+        (
+            "name=\"avatar\"; filename=\"me.png\"",
+            Some("image/png"),
+            &avatar,
+        ),
+        (
+            "name=\"notes\"; filename=\"notes.txt\"; filename*=UTF-8''n%C3%B6tes.txt",
+            None,
+            b"line 1\r\nline 2",
+        ),
+    ]);
+    let response = send(server.clone(), &post("/upload", MULTIPART, &body)).await;
+
+    assert!(
+        response.ends_with(
+            "Jürgen|[\"admin\", \"dev\"]|me.png|Some(\"image/png\")|102400|true|nötes.txt|line 1\r\nline 2"
+        ),
+        "{response}"
+    );
+
+    let mut limited = server.clone();
+    limited.body_limits(BodyLimits::new().max_file_size(50 * 1024));
+
+    let response = send(limited, &post("/upload", MULTIPART, &body)).await;
+
+    assert!(response.starts_with("HTTP/1.1 413"), "{response}");
+
+    // A text field has its own limit, and the files are not limited by it
+    let mut limited = server.clone();
+    limited.body_limits(BodyLimits::new().max_field_size(5));
+
+    let response = send(limited, &post("/upload", MULTIPART, &body)).await;
+
+    assert!(response.starts_with("HTTP/1.1 413"), "{response}");
+
+    let mut limited = server.clone();
+    limited.body_limits(BodyLimits::new().max_field_size(64));
+
+    let response = send(limited, &post("/upload", MULTIPART, &body)).await;
+
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+
+    let mut limited = server.clone();
+    limited.body_limits(BodyLimits::new().max_body_size(1024));
+
+    let response = send(limited, &post("/upload", MULTIPART, &body)).await;
+
+    assert!(response.starts_with("HTTP/1.1 413"), "{response}");
+
+    let unclosed = &body[..body.len() - 10];
+    let response = send(server, &post("/upload", MULTIPART, unclosed)).await;
+
+    assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+}
+
+const MULTIPART: &str = "multipart/form-data; boundary=\"----krustie\"";
+
+/// Returns a multipart body with the parameters of the `Content-Disposition`, the
+/// `Content-Type` and the content of each part.
+fn multipart_body(parts: &[(&str, Option<&str>, &[u8])]) -> Vec<u8> {
+    let mut body = b"preamble\r\n".to_vec();
+
+    for (disposition, content_type, content) in parts {
+        body.extend(
+            format!("------krustie\r\nContent-Disposition: form-data; {disposition}\r\n")
+                .as_bytes(),
+        );
+
+        if let Some(content_type) = content_type {
+            body.extend(format!("Content-Type: {content_type}\r\n").as_bytes());
+        }
+
+        body.extend(b"\r\n");
+        body.extend(*content);
+        body.extend(b"\r\n");
+    }
+
+    body.extend(b"------krustie--\r\n");
+    body
+}
+
+/// Returns a raw POST request with the body.
+fn post(path: &str, content_type: &str, body: &[u8]) -> Vec<u8> {
+    let mut request = format!(
+        "POST {path} HTTP/1.1\r\nHost: localhost\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\r\n",
+        body.len()
+    )
+    .into_bytes();
+
+    request.extend_from_slice(body);
+    request
+}
+
+/// Sends the raw request to the server over TCP and returns the raw response.
+async fn send(mut server: Server, request: &[u8]) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let handle = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        server.handle_stream(&mut stream).await;
+    });
+
+    let mut client = TcpStream::connect(address).await.unwrap();
+    client.write_all(request).await.unwrap();
+
+    let mut response = String::new();
+    client.read_to_string(&mut response).await.unwrap();
+
+    drop(client);
+    handle.await.unwrap();
+
+    response
+}
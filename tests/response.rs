@@ -1,14 +1,9 @@
-use std::collections::HashMap;
-
 use krustie::{json, Response, StatusCode};
 
 #[test]
 fn serialize_response() {
     let mut default_response = Response::default();
-    let mut headers = HashMap::new();
-
-    headers.insert(String::from("Hello"), String::from("World"));
-    headers.insert(String::from("Meaning of the life"), String::from("42"));
+    let headers = [("Hello", "World"), ("Meaning of the life", "42")];
 
     let response = default_response
         .status(StatusCode::Ok)
@@ -17,7 +12,62 @@ fn serialize_response() {
         .body_json(json::json!({"message": "Hello, World!"}));
 
     assert_eq!(
-        "HTTP/1.1 200 OK\r\nContent-Length: 27\r\nContent-Type: application/json\r\nHello: World\r\nMeaning of the life: 42\r\nServer: Krustie\r\n\r\n{\"message\":\"Hello, World!\"}",
+        "HTTP/1.1 200 OK\r\nServer: Krustie\r\nHello: World\r\nMeaning of the life: 42\r\nContent-Type: application/json\r\nContent-Length: 27\r\n\r\n{\"message\":\"Hello, World!\"}",
+        response.to_string()
+    );
+}
+
+#[test]
+fn repeated_headers() {
+    let mut response = Response::default();
+
+    response
+        .status(StatusCode::Ok)
+        .set_header("Cache-Control", "no-cache")
+        .set_header("cache-control", "no-store")
+        .append_header("Set-Cookie", "theme=dark")
+        .append_header("Set-Cookie", "lang=en");
+
+    assert_eq!(
+        response.get_header("CACHE-CONTROL"),
+        Some(&"no-store".to_string())
+    );
+    assert_eq!(
+        "HTTP/1.1 200 OK\r\ncache-control: no-store\r\nSet-Cookie: theme=dark\r\nSet-Cookie: lang=en\r\n\r\n",
         response.to_string()
     );
 }
+
+#[test]
+fn headers_keep_the_order_they_were_set() {
+    let mut response = Response::default();
+
+    response
+        .status(StatusCode::Ok)
+        .set_header("X-Request-Id", "1")
+        .append_header("Set-Cookie", "theme=dark")
+        .set_header("X-Powered-By", "Krustie")
+        .append_header("Set-Cookie", "lang=en")
+        .set_header("x-request-id", "2");
+
+    assert_eq!(
+        "HTTP/1.1 200 OK\r\nx-request-id: 2\r\nSet-Cookie: theme=dark\r\nX-Powered-By: Krustie\r\nSet-Cookie: lang=en\r\n\r\n",
+        response.to_string()
+    );
+}
+
+#[test]
+fn error_statuses() {
+    let mut response = Response::default();
+
+    for status in [
+        StatusCode::NotFound,
+        StatusCode::NotAcceptable,
+        StatusCode::PayloadTooLarge,
+        StatusCode::UnprocessableEntity,
+    ] {
+        assert!(response.status(status).is_error());
+    }
+
+    assert!(!response.status(StatusCode::NotModified).is_error());
+}
@@ -1,4 +1,11 @@
-use krustie::{HttpMethod, Request, Response, Router, Server, StatusCode};
+use krustie::{
+    json::json,
+    middlewares::{GzipEncoder, ServeOpenApi},
+    response::ContentType,
+    Endpoint, HandlerResult, HostRouter, HttpMethod, Middleware, Request, Resource,
+    ResourceAction, Response, RouteDoc, Router, RouterError, Server, StatusCode, TrailingSlash,
+    UrlError,
+};
 
 #[test]
 fn router_parameters() {
@@ -88,3 +95,1271 @@ fn router_and_query_parameters() {
 
     Response::assert_eq(&expected_response, &response);
 }
+
+#[test]
+fn differently_named_parameter_branches() {
+    let mut server = Server::create();
+    let mut router = Router::new();
+
+    router
+        .get("/users/:id/posts", |req, res| {
+            res.status(StatusCode::Ok)
+                .body_text(req.get_param("id").unwrap());
+        })
+        .get("/users/:name/profile", |req, res| {
+            res.status(StatusCode::Ok)
+                .body_text(req.get_param("name").unwrap());
+        });
+
+    server.use_handler(router);
+
+    let request = Request::builder().path("/users/42/posts").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::Ok);
+    assert_eq!(response.get_body(), b"42");
+
+    let request = Request::builder().path("/users/marvin/profile").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::Ok);
+    assert_eq!(response.get_body(), b"marvin");
+}
+
+#[test]
+fn backtracking_from_literal_to_parameter() {
+    let mut server = Server::create();
+    let mut router = Router::new();
+
+    router
+        .get("/files/new", |_, res| {
+            res.status(StatusCode::Ok).body_text("new");
+        })
+        .get("/files/:id/edit", |req, res| {
+            res.status(StatusCode::Ok)
+                .body_text(req.get_param("id").unwrap());
+        });
+
+    server.use_handler(router);
+
+    let request = Request::builder().path("/files/new").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_body(), b"new");
+
+    let request = Request::builder().path("/files/new/edit").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::Ok);
+    assert_eq!(response.get_body(), b"new");
+
+    let request = Request::builder().path("/files/new/delete").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::NotFound);
+}
+
+#[test]
+fn subrouter_under_continuing_parameter_path() {
+    let mut server = Server::create();
+    let mut router = Router::new();
+    let mut comments_router = Router::new();
+
+    comments_router.get("/", |req, res| {
+        res.status(StatusCode::Ok)
+            .body_text(req.get_param("post_id").unwrap());
+    });
+
+    router.use_router("/posts/:post_id/comments", comments_router);
+
+    server.use_handler(router);
+
+    let request = Request::builder().path("/posts/7/comments").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::Ok);
+    assert_eq!(response.get_body(), b"7");
+}
+
+#[test]
+fn compiled_router_with_shared_prefixes() {
+    let mut router = Router::new();
+
+    router
+        .get("/user", |_, res| {
+            res.status(StatusCode::Ok).body_text("user");
+        })
+        .get("/users", |_, res| {
+            res.status(StatusCode::Ok).body_text("users");
+        })
+        .get("/users/:id", |req, res| {
+            res.status(StatusCode::Ok)
+                .body_text(req.get_param("id").unwrap());
+        })
+        .get("/uploads/:file", |req, res| {
+            res.status(StatusCode::Ok)
+                .body_text(req.get_param("file").unwrap());
+        });
+
+    router.compile();
+
+    let mut server = Server::create();
+    server.use_handler(router);
+
+    for (path, body) in [
+        ("/user", "user"),
+        ("/users", "users"),
+        ("/users/42", "42"),
+        ("/uploads/cat.png", "cat.png"),
+        ("/users/42?sort=asc", "42"),
+        ("/users//42", "42"),
+        ("//users/42", "42"),
+    ] {
+        let request = Request::builder().path(path).build();
+        let response = server.mock_request(request);
+
+        assert_eq!(response.get_status(), StatusCode::Ok, "{path}");
+        assert_eq!(response.get_body(), body.as_bytes(), "{path}");
+    }
+
+    let request = Request::builder().path("/use").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::NotFound);
+}
+
+#[test]
+fn routes_added_after_compile() {
+    let mut router = Router::new();
+
+    router.get("/", |_, res| {
+        res.status(StatusCode::Ok).body_text("root");
+    });
+
+    router.compile();
+
+    router.post("/items", |_, res| {
+        res.status(StatusCode::Created);
+    });
+
+    let mut server = Server::create();
+    server.use_handler(router);
+
+    let request = Request::builder()
+        .method(HttpMethod::POST)
+        .path("/items")
+        .build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::Created);
+}
+
+#[test]
+fn merging_routers_with_shared_prefixes() {
+    let mut main_router = Router::new();
+    let mut users_router = Router::new();
+    let mut admin_router = Router::new();
+
+    users_router
+        .get("/", |_, res| {
+            res.status(StatusCode::Ok).body_text("list");
+        })
+        .get("/:id", |req, res| {
+            res.status(StatusCode::Ok)
+                .body_text(req.get_param("id").unwrap());
+        });
+
+    admin_router
+        .post("/", |_, res| {
+            res.status(StatusCode::Created);
+        })
+        .delete("/:id", |_, res| {
+            res.status(StatusCode::NoContent);
+        });
+
+    main_router.get("/", |_, res| {
+        res.status(StatusCode::Ok).body_text("root");
+    });
+    main_router.use_router("/users", users_router);
+    main_router.use_router("/users", admin_router);
+
+    let mut server = Server::create();
+    server.use_handler(main_router);
+
+    for (method, path, status) in [
+        (HttpMethod::GET, "/", StatusCode::Ok),
+        (HttpMethod::GET, "/users", StatusCode::Ok),
+        (HttpMethod::POST, "/users", StatusCode::Created),
+        (HttpMethod::GET, "/users/42", StatusCode::Ok),
+        (HttpMethod::DELETE, "/users/42", StatusCode::NoContent),
+    ] {
+        let request = Request::builder().method(method).path(path).build();
+        let response = server.mock_request(request);
+
+        assert_eq!(response.get_status(), status, "{path}");
+    }
+}
+
+#[test]
+fn mounting_router_at_root() {
+    let mut main_router = Router::new();
+    let mut feature_router = Router::new();
+
+    feature_router
+        .get("/", |_, res| {
+            res.status(StatusCode::Ok).body_text("feature root");
+        })
+        .get("/feature", |_, res| {
+            res.status(StatusCode::Ok).body_text("feature");
+        });
+
+    main_router.use_router("/", feature_router);
+
+    let mut server = Server::create();
+    server.use_handler(main_router);
+
+    let request = Request::builder().path("/").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_body(), b"feature root");
+
+    let request = Request::builder().path("/feature").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_body(), b"feature");
+}
+
+#[test]
+#[should_panic(expected = "Route already exist: GET /users")]
+fn merging_conflicting_routes() {
+    let mut main_router = Router::new();
+    let mut other_router = Router::new();
+
+    main_router.get("/users", |_, res| {
+        res.status(StatusCode::Ok);
+    });
+    other_router.get("/", |_, res| {
+        res.status(StatusCode::Ok);
+    });
+
+    main_router.use_router("/users", other_router);
+}
+
+#[test]
+fn duplicate_and_conflicting_routes() {
+    let mut router = Router::new();
+
+    fn get(_: &Request, res: &mut Response) {
+        res.status(StatusCode::Ok);
+    }
+
+    assert_eq!(
+        router.try_use_endpoint("/users/:id", Endpoint::new(HttpMethod::GET, get)),
+        Ok(())
+    );
+    assert_eq!(
+        router.try_use_endpoint("/users/:id/", Endpoint::new(HttpMethod::GET, get)),
+        Err(RouterError::DuplicateRoute {
+            method: HttpMethod::GET,
+            path: "/users/:id".to_string(),
+        })
+    );
+    assert_eq!(
+        router.try_use_endpoint("/users/:name", Endpoint::new(HttpMethod::GET, get)),
+        Err(RouterError::ConflictingParameters {
+            method: HttpMethod::GET,
+            path: "/users/:name".to_string(),
+            existing: "/users/:id".to_string(),
+        })
+    );
+    assert_eq!(
+        router.try_use_endpoint("/users/:name", Endpoint::new(HttpMethod::PUT, get)),
+        Ok(())
+    );
+    assert_eq!(
+        router.try_use_endpoint("/users/:", Endpoint::new(HttpMethod::GET, get)),
+        Err(RouterError::InvalidSegment(":".to_string()))
+    );
+    assert_eq!(
+        router.try_use_endpoint("/a/:id/b/:id", Endpoint::new(HttpMethod::GET, get)),
+        Err(RouterError::DuplicateParameter {
+            path: "/a/:id/b/:id".to_string(),
+            param: "id".to_string(),
+        })
+    );
+
+    let mut posts_router = Router::new();
+    posts_router.get("/posts/:id", get);
+
+    assert_eq!(
+        router.try_use_router("/users/:id", posts_router),
+        Err(RouterError::DuplicateParameter {
+            path: "/users/:id/posts/:id".to_string(),
+            param: "id".to_string(),
+        })
+    );
+}
+
+#[test]
+fn conflicting_subrouter_is_not_merged() {
+    let mut main_router = Router::new();
+    let mut user_router = Router::new();
+
+    main_router.get("/users/:id/posts", |_, res| {
+        res.status(StatusCode::Ok);
+    });
+
+    user_router
+        .post("/", |_, res| {
+            res.status(StatusCode::Created);
+        })
+        .get("/posts", |_, res| {
+            res.status(StatusCode::Ok);
+        });
+
+    assert_eq!(
+        main_router.try_use_router("/users/:user_id", user_router),
+        Err(RouterError::ConflictingParameters {
+            method: HttpMethod::GET,
+            path: "/users/:user_id/posts".to_string(),
+            existing: "/users/:id/posts".to_string(),
+        })
+    );
+
+    let mut server = Server::create();
+    server.use_handler(main_router);
+
+    let request = Request::builder()
+        .method(HttpMethod::POST)
+        .path("/users/42")
+        .build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::NotFound);
+}
+
+#[test]
+fn fallbacks_for_nested_routers() {
+    let mut main_router = Router::new();
+    let mut api_router = Router::new();
+    let mut users_router = Router::new();
+
+    users_router.get("/:id", |_, res| {
+        res.status(StatusCode::Ok);
+    });
+
+    api_router
+        .use_fallback(|_, res| {
+            res.status(StatusCode::NotFound)
+                .body_json(json!({ "error": "Not Found" }));
+        })
+        .use_router("/users", users_router);
+
+    main_router
+        .use_fallback(|_, res| {
+            res.status(StatusCode::Ok)
+                .body(b"index".to_vec(), ContentType::Html);
+        })
+        .get("/", |_, res| {
+            res.status(StatusCode::Ok).body_text("root");
+        })
+        .use_router("/api", api_router);
+
+    let mut server = Server::create();
+    server.use_handler(main_router);
+
+    let request = Request::builder().path("/api/users/42").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::Ok);
+
+    for path in ["/api/unknown", "/api/users/42/posts", "/api"] {
+        let request = Request::builder().path(path).build();
+        let response = server.mock_request(request);
+
+        assert_eq!(response.get_status(), StatusCode::NotFound, "{path}");
+        assert_eq!(
+            response.get_header("Content-Type"),
+            Some(&"application/json".to_string()),
+            "{path}"
+        );
+    }
+
+    for path in ["/dashboard", "/dashboard/settings"] {
+        let request = Request::builder().path(path).build();
+        let response = server.mock_request(request);
+
+        assert_eq!(response.get_status(), StatusCode::Ok, "{path}");
+        assert_eq!(response.get_body(), b"index", "{path}");
+    }
+}
+
+#[test]
+fn conflicting_fallbacks_are_not_merged() {
+    let mut main_router = Router::new();
+    let mut api_router = Router::new();
+    let mut other_router = Router::new();
+
+    api_router.use_fallback(|_, res| {
+        res.status(StatusCode::NotFound).body_text("api");
+    });
+    other_router
+        .get("/other", |_, res| {
+            res.status(StatusCode::Ok);
+        })
+        .use_fallback(|_, res| {
+            res.status(StatusCode::NotFound).body_text("other");
+        });
+
+    main_router.use_router("/api", api_router);
+
+    assert_eq!(
+        main_router.try_use_router("/", other_router.clone()),
+        Ok(())
+    );
+    assert_eq!(
+        main_router.try_use_router("/api", other_router),
+        Err(RouterError::DuplicateFallback("/api".to_string()))
+    );
+
+    let mut server = Server::create();
+    server.use_handler(main_router);
+
+    for (path, body) in [("/api/missing", "api"), ("/missing", "other")] {
+        let request = Request::builder().path(path).build();
+        let response = server.mock_request(request);
+
+        assert_eq!(response.get_status(), StatusCode::NotFound);
+        assert_eq!(response.get_body(), body.as_bytes(), "{path}");
+    }
+}
+
+#[test]
+fn not_found_without_fallback() {
+    let mut router = Router::new();
+
+    router.get("/", |_, res| {
+        res.status(StatusCode::Ok);
+    });
+
+    let mut server = Server::create();
+    server.use_handler(router);
+
+    let request = Request::builder().path("/unknown").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::NotFound);
+    assert!(response.get_body().is_empty());
+}
+
+#[test]
+fn multiple_methods_under_parameter() {
+    let mut router = Router::new();
+
+    router
+        .get("/users/:id", |_, res| {
+            res.status(StatusCode::Ok).body_text("get");
+        })
+        .put("/users/:id", |_, res| {
+            res.status(StatusCode::Ok).body_text("put");
+        });
+
+    let mut server = Server::create();
+    server.use_handler(router);
+
+    let request = Request::builder()
+        .method(HttpMethod::PUT)
+        .path("/users/42")
+        .build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_body(), b"put");
+}
+
+#[test]
+fn trailing_slash_policies() {
+    let mut router = Router::new();
+    let mut strict_router = Router::new();
+    let mut redirect_router = Router::new();
+
+    router.get("/lenient", |_, res| {
+        res.status(StatusCode::Ok);
+    });
+
+    strict_router
+        .trailing_slash(TrailingSlash::Strict)
+        .get("/", |_, res| {
+            res.status(StatusCode::Ok);
+        });
+
+    redirect_router
+        .trailing_slash(TrailingSlash::PermanentRedirect)
+        .get("/users", |_, res| {
+            res.status(StatusCode::Ok);
+        });
+
+    router.use_router("/strict", strict_router);
+    router.use_router("/redirect", redirect_router);
+
+    let mut server = Server::create();
+    server.use_handler(router);
+
+    let mut status = |path: &str| {
+        let request = Request::builder().path(path).build();
+        server.mock_request(request).get_status()
+    };
+
+    assert_eq!(status("/lenient/"), StatusCode::Ok);
+    assert_eq!(status("/strict"), StatusCode::Ok);
+    assert_eq!(status("/strict/"), StatusCode::NotFound);
+    assert_eq!(status("/redirect/users"), StatusCode::Ok);
+
+    let request = Request::builder().path("/redirect/users/?page=2").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::PermanentRedirect);
+    assert_eq!(
+        response.get_header("Location").map(String::as_str),
+        Some("/redirect/users?page=2")
+    );
+}
+
+#[test]
+fn case_insensitive_routes() {
+    let mut router = Router::new();
+    let mut users_router = Router::new();
+
+    users_router
+        .case_insensitive(true)
+        .get("/:id/Posts", |req, res| {
+            res.status(StatusCode::Ok)
+                .body_text(req.get_param("id").unwrap());
+        });
+
+    router
+        .get("/About", |_, res| {
+            res.status(StatusCode::Ok);
+        })
+        .use_router("/users", users_router);
+
+    let mut server = Server::create();
+    server.use_handler(router);
+
+    let request = Request::builder().path("/users/Ab/POSTS").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::Ok);
+    assert_eq!(response.get_body(), b"Ab");
+
+    let request = Request::builder().path("/USERS/Ab/posts").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::NotFound);
+
+    let request = Request::builder().path("/about").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::NotFound);
+}
+
+#[test]
+fn named_routes() {
+    let mut router = Router::new();
+    let mut users_router = Router::new();
+
+    users_router
+        .get("/:id/posts/:post_id", |_, res| {
+            res.status(StatusCode::Ok);
+        })
+        .name("user.post");
+
+    router
+        .get("/", |_, res| {
+            res.status(StatusCode::Ok);
+        })
+        .name("home");
+    router.use_router("/users", users_router);
+
+    assert_eq!(router.url_for("home", []), Ok("/".to_string()));
+    assert_eq!(
+        router.url_for("user.post", [("post_id", "7"), ("id", "a/b"), ("q", "x&y")]),
+        Ok("/users/a%2Fb/posts/7?q=x%26y".to_string())
+    );
+    assert_eq!(
+        router.url_for("user.post", [("id", "1")]),
+        Err(UrlError::MissingParameter {
+            name: "user.post".to_string(),
+            param: "post_id".to_string()
+        })
+    );
+    assert_eq!(
+        router.url_for("user.edit", []),
+        Err(UrlError::UnknownRoute("user.edit".to_string()))
+    );
+
+    let mut other_router = Router::new();
+
+    other_router
+        .get("/", |_, res| {
+            res.status(StatusCode::Ok);
+        })
+        .name("home");
+
+    assert_eq!(
+        router.try_use_router("/other", other_router),
+        Err(RouterError::DuplicateName("home".to_string()))
+    );
+}
+
+#[test]
+#[should_panic(expected = "No endpoint is added right before users")]
+fn naming_after_mounting_a_router() {
+    let mut router = Router::new();
+
+    router.get("/", |_, res| {
+        res.status(StatusCode::Ok);
+    });
+    router.use_router("/users", Router::new());
+    router.name("users");
+}
+
+#[test]
+fn route_table() {
+    #[derive(Clone)]
+    struct Auth;
+
+    impl Middleware for Auth {}
+
+    let mut router = Router::new();
+    let mut admin_router = Router::new();
+
+    admin_router.use_middleware(Auth).use_endpoint(
+        "/users/:id",
+        Endpoint::new_with_middleware(HttpMethod::DELETE, ok, vec![Box::new(GzipEncoder)]),
+    );
+    admin_router.name("admin.user.delete");
+
+    router.get("/", ok).post("/users", ok);
+    router.use_router("/admin", admin_router);
+
+    let routes = router.routes();
+    let paths = routes
+        .get_routes()
+        .iter()
+        .map(|route| format!("{} {}", route.get_method(), route.get_path()))
+        .collect::<Vec<String>>();
+
+    assert_eq!(paths, ["GET /", "DELETE /admin/users/:id", "POST /users"]);
+    assert_eq!(
+        routes.to_string(),
+        "METHOD  PATH              NAME               MIDDLEWARES\n\
+         GET     /\n\
+         DELETE  /admin/users/:id  admin.user.delete  Auth, GzipEncoder\n\
+         POST    /users"
+    );
+}
+
+#[test]
+fn openapi_document() {
+    let mut router = Router::new();
+    let mut users_router = Router::new();
+
+    users_router
+        .get("/:id", ok)
+        .name("user.show")
+        .doc(
+            RouteDoc::new()
+                .summary("Returns a user")
+                .tag("users")
+                .param("id", "Id of the user")
+                .query("fields", "Fields to return")
+                .response(
+                    StatusCode::Ok,
+                    "The user",
+                    Some(json!({ "type": "object" })),
+                ),
+        )
+        .post("/", ok)
+        .doc(RouteDoc::new().request_body(json!({ "type": "object" })));
+
+    router.use_router("/users", users_router);
+
+    let document = router.openapi("Users API", "1.0.0");
+
+    assert_eq!(
+        document,
+        json!({
+            "openapi": "3.1.0",
+            "info": { "title": "Users API", "version": "1.0.0" },
+            "paths": {
+                "/users": {
+                    "post": {
+                        "requestBody": {
+                            "required": true,
+                            "content": { "application/json": { "schema": { "type": "object" } } }
+                        },
+                        "responses": { "default": { "description": "Default response" } }
+                    }
+                },
+                "/users/{id}": {
+                    "get": {
+                        "operationId": "user.show",
+                        "summary": "Returns a user",
+                        "tags": ["users"],
+                        "parameters": [
+                            {
+                                "name": "id",
+                                "in": "path",
+                                "required": true,
+                                "description": "Id of the user",
+                                "schema": { "type": "string" }
+                            },
+                            {
+                                "name": "fields",
+                                "in": "query",
+                                "required": false,
+                                "description": "Fields to return",
+                                "schema": { "type": "string" }
+                            }
+                        ],
+                        "responses": {
+                            "200": {
+                                "description": "The user",
+                                "content": { "application/json": { "schema": { "type": "object" } } }
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    );
+
+    let mut server = Server::create();
+    server.use_handler(ServeOpenApi::new("/openapi.json", document.clone()));
+    server.use_handler(router);
+
+    let request = Request::builder().path("/openapi.json").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_status(), StatusCode::Ok);
+    assert_eq!(response.get_body(), document.to_string().as_bytes());
+}
+
+#[test]
+fn openapi_paths_with_different_parameter_names() {
+    let mut router = Router::new();
+
+    router
+        .get("/users/:id", ok)
+        .delete("/users/:name", ok)
+        .doc(RouteDoc::new().param("name", "Name of the user"));
+
+    let document = router.openapi("Users API", "1.0.0");
+    let paths = document["paths"].as_object().unwrap();
+
+    assert_eq!(paths.keys().collect::<Vec<&String>>(), ["/users/{id}"]);
+    assert_eq!(
+        paths["/users/{id}"]["delete"]["parameters"],
+        json!([{
+            "name": "id",
+            "in": "path",
+            "required": true,
+            "description": "Name of the user",
+            "schema": { "type": "string" }
+        }])
+    );
+}
+
+#[test]
+fn openapi_guarded_endpoints() {
+    let mut router = Router::new();
+
+    router.use_endpoint("/users", Endpoint::new(HttpMethod::GET, ok));
+    router.doc(
+        RouteDoc::new()
+            .summary("Lists the users")
+            .tag("users")
+            .query("page", "Page of the users")
+            .response(StatusCode::Ok, "The users", None),
+    );
+    router.use_endpoint(
+        "/users",
+        Endpoint::new(HttpMethod::GET, ok).header_eq("x-api-version", "2"),
+    );
+    router.doc(
+        RouteDoc::new()
+            .summary("Lists the users in version 2")
+            .tag("v2")
+            .query("page", "Page of the users")
+            .query("limit", "Number of the users")
+            .response(StatusCode::NotAcceptable, "Unknown version", None),
+    );
+    router.use_endpoint(
+        "/users",
+        Endpoint::new(HttpMethod::GET, ok).query_eq("format", "csv"),
+    );
+
+    let document = router.openapi("Users API", "1.0.0");
+    let operation = &document["paths"]["/users"]["get"];
+
+    assert_eq!(operation["summary"], json!("Lists the users"));
+    assert_eq!(operation["tags"], json!(["users", "v2"]));
+    assert_eq!(
+        operation["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|param| param["name"].as_str().unwrap())
+            .collect::<Vec<&str>>(),
+        ["page", "limit"]
+    );
+    assert_eq!(
+        operation["responses"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .collect::<Vec<&String>>(),
+        ["200", "406"]
+    );
+}
+
+#[test]
+fn route_builder() {
+    #[derive(Clone)]
+    struct Tag;
+
+    impl Middleware for Tag {
+        fn middleware(&mut self, _: &Request, res: &mut Response) -> HandlerResult {
+            res.set_header("X-Tag", "tag");
+            HandlerResult::Next
+        }
+    }
+
+    let mut router = Router::new();
+
+    router
+        .route("/users/:id")
+        .get(|req, res| {
+            res.status(StatusCode::Ok)
+                .body_text(req.get_param("id").unwrap());
+        })
+        .middleware(Tag)
+        .methods(&[HttpMethod::PUT, HttpMethod::PATCH], ok);
+
+    router.route("/ping").any(ok);
+
+    let methods = router
+        .routes()
+        .get_routes()
+        .iter()
+        .map(|route| {
+            format!(
+                "{} {} {:?}",
+                route.get_method(),
+                route.get_path(),
+                route.get_middlewares()
+            )
+        })
+        .collect::<Vec<String>>();
+
+    assert_eq!(
+        methods,
+        [
+            "DELETE /ping []",
+            "GET /ping []",
+            "PATCH /ping []",
+            "POST /ping []",
+            "PUT /ping []",
+            "GET /users/:id [\"Tag\"]",
+            "PATCH /users/:id [\"Tag\"]",
+            "PUT /users/:id [\"Tag\"]",
+        ]
+    );
+
+    let mut server = Server::create();
+    server.use_handler(router);
+
+    let request = Request::builder().path("/users/42").build();
+    let response = server.mock_request(request);
+
+    assert_eq!(response.get_body(), b"42");
+    assert_eq!(
+        response.get_header("X-Tag").map(String::as_str),
+        Some("tag")
+    );
+}
+
+#[test]
+fn resources() {
+    #[derive(Clone)]
+    struct Users;
+
+    impl Resource for Users {
+        fn index(&self) -> Option<ResourceAction<Self>> {
+            Some(|_, _, res| {
+                res.status(StatusCode::Ok).body_text("users");
+            })
+        }
+
+        fn show(&self) -> Option<ResourceAction<Self>> {
+            Some(|_, req, res| {
+                res.status(StatusCode::Ok)
+                    .body_text(req.get_param("id").unwrap());
+            })
+        }
+    }
+
+    #[derive(Clone)]
+    struct Posts;
+
+    impl Posts {
+        fn remove(&mut self, req: &Request, res: &mut Response) {
+            let user_id = req.get_param("user_id").unwrap();
+            let id = req.get_param("id").unwrap();
+
+            res.status(StatusCode::Ok)
+                .body_text(&format!("{user_id}/{id}"));
+        }
+    }
+
+    impl Resource for Posts {
+        fn destroy(&self) -> Option<ResourceAction<Self>> {
+            Some(Self::remove)
+        }
+    }
+
+    let mut router = Router::new();
+
+    router
+        .resource("/users", Users)
+        .resource("/users/:user_id/posts", Posts);
+
+    let mut server = Server::create();
+    server.use_handler(router);
+
+    let mut send = |method: HttpMethod, path: &str| {
+        let request = Request::builder().method(method).path(path).build();
+        let response = server.mock_request(request);
+
+        (
+            response.get_status(),
+            String::from_utf8_lossy(response.get_body()).to_string(),
+            response.get_header("Allow").map(|allow| allow.to_string()),
+        )
+    };
+
+    let ok = |body: &str| (StatusCode::Ok, body.to_string(), None);
+    let not_allowed = |allow: &str| {
+        (
+            StatusCode::MethodNotAllowed,
+            String::new(),
+            Some(allow.to_string()),
+        )
+    };
+
+    assert_eq!(send(HttpMethod::GET, "/users"), ok("users"));
+    assert_eq!(send(HttpMethod::GET, "/users/7"), ok("7"));
+    assert_eq!(send(HttpMethod::POST, "/users"), not_allowed("GET"));
+    assert_eq!(send(HttpMethod::PUT, "/users/7"), not_allowed("GET"));
+    assert_eq!(send(HttpMethod::DELETE, "/users/7/posts/3"), ok("7/3"));
+    assert_eq!(
+        send(HttpMethod::GET, "/users/7/posts"),
+        (StatusCode::MethodNotAllowed, String::new(), None)
+    );
+    assert_eq!(
+        send(HttpMethod::PATCH, "/users/7/posts/3"),
+        not_allowed("DELETE")
+    );
+}
+
+#[test]
+#[should_panic(expected = "more than one parameter named id")]
+fn resource_under_an_id_parameter() {
+    #[derive(Clone)]
+    struct Posts;
+
+    impl Resource for Posts {
+        fn show(&self) -> Option<ResourceAction<Self>> {
+            Some(|_, _, res| {
+                res.status(StatusCode::Ok);
+            })
+        }
+    }
+
+    Router::new().resource("/users/:id/posts", Posts);
+}
+
+#[test]
+fn resource_with_a_single_action() {
+    #[derive(Clone)]
+    struct Users;
+
+    impl Resource for Users {
+        fn index(&self) -> Option<ResourceAction<Self>> {
+            Some(|_, _, res| {
+                res.status(StatusCode::Ok).body_text("users");
+            })
+        }
+    }
+
+    let mut router = Router::new();
+    router.resource("/users", Users);
+
+    let routes = router
+        .routes()
+        .get_routes()
+        .iter()
+        .map(|route| format!("{} {}", route.get_method(), route.get_path()))
+        .collect::<Vec<String>>();
+
+    assert_eq!(routes, ["GET /users"]);
+
+    let document = router.openapi("Users API", "1.0.0");
+    let paths = document["paths"].as_object().unwrap();
+
+    assert_eq!(paths.keys().collect::<Vec<_>>(), ["/users"]);
+    assert_eq!(
+        paths["/users"].as_object().unwrap().keys().collect::<Vec<_>>(),
+        ["get"]
+    );
+
+    // There is no member route that the new route would conflict with
+    assert!(router
+        .try_use_endpoint("/users/:slug", Endpoint::new(HttpMethod::GET, ok))
+        .is_ok());
+
+    let mut server = Server::create();
+    server.use_handler(router);
+
+    let mut send = |method: HttpMethod, path: &str| {
+        let request = Request::builder().method(method).path(path).build();
+        let response = server.mock_request(request);
+
+        (
+            response.get_status(),
+            response.get_header("Allow").map(|allow| allow.to_string()),
+        )
+    };
+
+    assert_eq!(send(HttpMethod::GET, "/users"), (StatusCode::Ok, None));
+    assert_eq!(
+        send(HttpMethod::POST, "/users"),
+        (StatusCode::MethodNotAllowed, Some("GET".to_string()))
+    );
+    assert_eq!(
+        send(HttpMethod::DELETE, "/users/1"),
+        (StatusCode::NotFound, None)
+    );
+    assert_eq!(send(HttpMethod::GET, "/users/1"), (StatusCode::Ok, None));
+}
+
+#[test]
+fn virtual_hosts() {
+    let mut main_router = Router::new();
+    let mut tenant_router = Router::new();
+    let mut admin_router = Router::new();
+
+    main_router.get("/", |_, res| {
+        res.status(StatusCode::Ok).body_text("main");
+    });
+    tenant_router.get("/users/:id", |req, res| {
+        let tenant = req.get_param("subdomain").unwrap();
+        let id = req.get_param("id").unwrap();
+
+        res.status(StatusCode::Ok)
+            .body_text(&format!("{tenant} {id}"));
+    });
+    admin_router.get("/", |_, res| {
+        res.status(StatusCode::Ok).body_text("admin");
+    });
+
+    let mut hosts = HostRouter::new();
+
+    hosts
+        .host("example.com", main_router)
+        .host("*.example.com", tenant_router)
+        .host("*.admin.example.com:8080", admin_router)
+        .unknown_host_status(StatusCode::NotFound);
+
+    let mut server = Server::create();
+    server.use_handler(hosts);
+
+    let mut send = |host: Option<&str>, path: &str| {
+        let mut builder = Request::builder();
+        builder.path(path);
+
+        if let Some(host) = host {
+            builder.header("Host", host);
+        }
+
+        let response = server.mock_request(builder.build());
+
+        (
+            response.get_status(),
+            String::from_utf8_lossy(response.get_body()).to_string(),
+        )
+    };
+
+    assert_eq!(
+        send(Some("Example.com:8080"), "/"),
+        (StatusCode::Ok, "main".to_string())
+    );
+    assert_eq!(
+        send(Some("acme.example.com"), "/users/7"),
+        (StatusCode::Ok, "acme 7".to_string())
+    );
+    assert_eq!(
+        send(Some("eu.admin.example.com"), "/"),
+        (StatusCode::Ok, "admin".to_string())
+    );
+    assert_eq!(send(Some("example.org"), "/").0, StatusCode::NotFound);
+    assert_eq!(send(None, "/").0, StatusCode::NotFound);
+
+    // The host of an absolute-form target takes precedence over the Host header
+    assert_eq!(
+        send(Some("example.org"), "http://acme.example.com:8080/users/7"),
+        (StatusCode::Ok, "acme 7".to_string())
+    );
+    assert_eq!(
+        send(None, "http://user@Example.com/"),
+        (StatusCode::Ok, "main".to_string())
+    );
+}
+
+#[test]
+fn endpoint_guards() {
+    let mut router = Router::new();
+
+    router.get("/users", |_, res| {
+        res.status(StatusCode::Ok).body_text("v1");
+    });
+    router.use_endpoint(
+        "/users",
+        Endpoint::new(HttpMethod::GET, |_, res| {
+            res.status(StatusCode::Ok).body_text("v2");
+        })
+        .header_eq("x-api-version", "2"),
+    );
+    router.use_endpoint(
+        "/report",
+        Endpoint::new(HttpMethod::GET, |_, res| {
+            res.status(StatusCode::Ok).body_text("csv");
+        })
+        .accepts("text/csv"),
+    );
+    router.use_endpoint(
+        "/report",
+        Endpoint::new(HttpMethod::GET, |_, res| {
+            res.status(StatusCode::Ok).body_text("json");
+        })
+        .accepts("application/json"),
+    );
+    router.use_endpoint(
+        "/upload",
+        Endpoint::new(HttpMethod::POST, |_, res| {
+            res.status(StatusCode::Ok).body_text("uploaded");
+        })
+        .content_type("application/json")
+        .guard(|req| req.get_query_param("dry_run").is_none()),
+    );
+    router.use_endpoint(
+        "/import",
+        Endpoint::new(HttpMethod::POST, |_, res| {
+            res.status(StatusCode::Ok).body_text("imported");
+        })
+        .content_type("Application/JSON; charset=utf-8"),
+    );
+
+    let mut server = Server::create();
+    server.use_handler(router);
+
+    let mut send = |method: HttpMethod, path: &str, headers: Vec<(&str, &str)>| {
+        let request = Request::builder()
+            .method(method)
+            .path(path)
+            .headers(headers)
+            .build();
+        let response = server.mock_request(request);
+
+        (
+            response.get_status(),
+            String::from_utf8_lossy(response.get_body()).to_string(),
+        )
+    };
+
+    assert_eq!(send(HttpMethod::GET, "/users", vec![]).1, "v1");
+    assert_eq!(
+        send(HttpMethod::GET, "/users", vec![("X-Api-Version", "2")]).1,
+        "v2"
+    );
+    assert_eq!(
+        send(HttpMethod::GET, "/report", vec![("Accept", "text/*")]).1,
+        "csv"
+    );
+    assert_eq!(
+        send(
+            HttpMethod::GET,
+            "/report",
+            vec![("Accept", "application/json;q=0.9")]
+        )
+        .1,
+        "json"
+    );
+    assert_eq!(
+        send(HttpMethod::GET, "/report", vec![("Accept", "image/png")]).0,
+        StatusCode::NotAcceptable
+    );
+
+    // The endpoint whose media type has the highest quality is run
+    for (accept, body) in [
+        ("text/csv;q=0.1, application/json", "json"),
+        ("application/json;q=0.1, text/csv", "csv"),
+        ("text/csv, application/json", "csv"),
+        ("*/*", "csv"),
+    ] {
+        assert_eq!(
+            send(HttpMethod::GET, "/report", vec![("Accept", accept)]).1,
+            body,
+            "{accept}"
+        );
+    }
+
+    assert_eq!(
+        send(
+            HttpMethod::POST,
+            "/import",
+            vec![("Content-Type", "application/json")]
+        ),
+        (StatusCode::Ok, "imported".to_string())
+    );
+    assert_eq!(
+        send(
+            HttpMethod::POST,
+            "/upload",
+            vec![("Content-Type", "application/json; charset=utf-8")]
+        ),
+        (StatusCode::Ok, "uploaded".to_string())
+    );
+    assert_eq!(
+        send(
+            HttpMethod::POST,
+            "/upload",
+            vec![("Content-Type", "text/plain")]
+        )
+        .0,
+        StatusCode::UnsupportedMediaType
+    );
+    assert_eq!(
+        send(
+            HttpMethod::POST,
+            "/upload?dry_run=1",
+            vec![("Content-Type", "application/json")]
+        )
+        .0,
+        StatusCode::NotFound
+    );
+}
+
+fn ok(_: &Request, res: &mut Response) {
+    res.status(StatusCode::Ok);
+}